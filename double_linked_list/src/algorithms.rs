@@ -0,0 +1,9 @@
+//! Algorithms built on top of [Deque](crate::deque::Deque).
+
+pub mod monotonic;
+pub mod zero_one_bfs;
+
+pub use monotonic::{
+    sliding_window_max, sliding_window_min, Extreme, MonotonicQueue, SlidingWindow,
+};
+pub use zero_one_bfs::{zero_one_bfs, Weight};
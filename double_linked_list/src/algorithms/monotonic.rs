@@ -0,0 +1,231 @@
+use crate::deque::Deque;
+
+/// Which extreme a [MonotonicQueue] keeps track of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extreme {
+    Min,
+    Max,
+}
+
+impl Extreme {
+    /// Whether `new` makes `old` irrelevant, because it is at least as extreme and newer
+    fn supersedes<T: Ord>(&self, new: &T, old: &T) -> bool {
+        match self {
+            Extreme::Min => new <= old,
+            Extreme::Max => new >= old,
+        }
+    }
+}
+
+/// Tracks the minimum or maximum of a window of values that slides over a stream.
+///
+/// Every pushed value gets a position, counting up from 0, and values are expired by
+/// position. Values that can never become the extreme, because a newer value is at least
+/// as extreme, are dropped right away, so the queue holds values in monotonic order and
+/// every operation takes amortised O(1).
+#[derive(Debug, Clone)]
+pub struct MonotonicQueue<T> {
+    extreme: Extreme,
+    // the candidates with their positions, the current extreme on the left
+    candidates: Deque<(usize, T)>,
+    pushed: usize,
+}
+
+impl<T: Ord> MonotonicQueue<T> {
+    pub fn create(extreme: Extreme) -> MonotonicQueue<T> {
+        MonotonicQueue {
+            extreme,
+            candidates: Deque::create(),
+            pushed: 0,
+        }
+    }
+
+    /// Adds a value to the window, returning its position
+    pub fn push(&mut self, x: T) -> usize {
+        while let Some((_, last)) = self.candidates.last().map(|item| item.value()) {
+            if !self.extreme.supersedes(&x, last) {
+                break;
+            }
+            self.candidates.pop_right();
+        }
+
+        let position = self.pushed;
+        self.candidates.push_right((position, x));
+        self.pushed += 1;
+
+        position
+    }
+
+    /// Expires every value pushed before position `start`, the start of the window
+    pub fn pop_expired(&mut self, start: usize) {
+        while let Some((position, _)) = self.candidates.first().map(|item| item.value()) {
+            if *position >= start {
+                break;
+            }
+            self.candidates.pop_left();
+        }
+    }
+
+    /// The extreme of the values in the window, or `None` when they have all expired
+    pub fn current_extreme(&self) -> Option<&T> {
+        self.candidates.first().map(|item| &item.value().1)
+    }
+
+    /// The number of values pushed so far, which is the position of the next value
+    pub fn pushed(&self) -> usize {
+        self.pushed
+    }
+}
+
+/// Yields the extreme of every window of `k` consecutive values, see [sliding_window_min]
+pub struct SlidingWindow<I: Iterator> {
+    values: I,
+    queue: MonotonicQueue<I::Item>,
+    k: usize,
+}
+
+impl<I> Iterator for SlidingWindow<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the first window needs `k` values, every later window one more
+        loop {
+            let position = self.queue.push(self.values.next()?);
+            if position + 1 >= self.k {
+                self.queue.pop_expired(position + 1 - self.k);
+                return self.queue.current_extreme().cloned();
+            }
+        }
+    }
+}
+
+fn sliding_window<I>(values: I, k: usize, extreme: Extreme) -> SlidingWindow<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Ord + Clone,
+{
+    assert!(k > 0, "A window needs room for at least one value");
+
+    SlidingWindow {
+        values: values.into_iter(),
+        queue: MonotonicQueue::create(extreme),
+        k,
+    }
+}
+
+/// The minimum of every window of `k` consecutive values, in O(n) overall.
+///
+/// Yields nothing when there are fewer than `k` values.
+///
+/// # Panics
+///
+/// Panics when `k` is 0.
+pub fn sliding_window_min<I>(values: I, k: usize) -> SlidingWindow<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Ord + Clone,
+{
+    sliding_window(values, k, Extreme::Min)
+}
+
+/// The maximum of every window of `k` consecutive values, see [sliding_window_min]
+pub fn sliding_window_max<I>(values: I, k: usize) -> SlidingWindow<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Ord + Clone,
+{
+    sliding_window(values, k, Extreme::Max)
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::algorithms::monotonic::{
+            sliding_window_max, sliding_window_min, Extreme, MonotonicQueue,
+        };
+
+        #[test]
+        fn sliding_minimums() {
+            let minimums: Vec<_> = sliding_window_min(vec![4, 2, 12, 3, 8, 1, 7], 3).collect();
+            assert_eq!(minimums, vec![2, 2, 3, 1, 1]);
+        }
+
+        #[test]
+        fn sliding_maximums() {
+            let maximums: Vec<_> = sliding_window_max([1, 3, -1, -3, 5, 3, 6, 7], 3).collect();
+            assert_eq!(maximums, vec![3, 3, 5, 5, 6, 7]);
+        }
+
+        #[test]
+        fn windows_of_one_value_yield_every_value() {
+            let values = vec![3, 1, 2];
+            assert_eq!(
+                sliding_window_min(values.clone(), 1).collect::<Vec<_>>(),
+                values
+            );
+        }
+
+        #[test]
+        fn too_few_values_yield_nothing() {
+            assert_eq!(sliding_window_max(vec![1, 2], 3).next(), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn an_empty_window_is_refused() {
+            let _ = sliding_window_min(vec![1], 0);
+        }
+
+        #[test]
+        fn equal_values_expire_one_at_a_time() {
+            let mut queue = MonotonicQueue::create(Extreme::Max);
+            queue.push(5);
+            queue.push(5);
+            queue.push(1);
+
+            queue.pop_expired(1);
+            assert_eq!(queue.current_extreme(), Some(&5));
+            queue.pop_expired(2);
+            assert_eq!(queue.current_extreme(), Some(&1));
+            queue.pop_expired(3);
+            assert_eq!(queue.current_extreme(), None);
+            assert_eq!(queue.pushed(), 3);
+        }
+
+        #[test]
+        fn streaming_readings_by_position() {
+            let mut queue = MonotonicQueue::create(Extreme::Min);
+            let readings = [(20, "a"), (18, "b"), (25, "c"), (19, "d")];
+            let mut extremes = vec![];
+            for reading in readings {
+                let position = queue.push(reading);
+                queue.pop_expired((position + 1).saturating_sub(2));
+                extremes.push(queue.current_extreme().unwrap().1);
+            }
+
+            assert_eq!(extremes, vec!["a", "b", "b", "d"]);
+        }
+    }
+
+    mod property_tests {
+        use crate::algorithms::monotonic::{sliding_window_max, sliding_window_min};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn windows_match_a_brute_force_search(values: Vec<i8>, k in 1..10usize) {
+                let minimums: Vec<i8> = values.windows(k).map(|w| *w.iter().min().unwrap()).collect();
+                let maximums: Vec<i8> = values.windows(k).map(|w| *w.iter().max().unwrap()).collect();
+
+                prop_assert_eq!(sliding_window_min(values.clone(), k).collect::<Vec<_>>(), minimums);
+                prop_assert_eq!(sliding_window_max(values, k).collect::<Vec<_>>(), maximums);
+            }
+        }
+    }
+}
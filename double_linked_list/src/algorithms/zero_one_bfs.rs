@@ -0,0 +1,167 @@
+use crate::deque::Deque;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The weight of an edge in a graph searched by [zero_one_bfs]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weight {
+    Zero,
+    One,
+}
+
+/// The shortest distance from `start` to every reachable node, in a graph whose edges
+/// weigh 0 or 1.
+///
+/// `neighbours` returns the edges leaving a node. Nodes reached over an edge of weight 0
+/// are pushed to the left of the deque and the others to the right, so the deque stays
+/// ordered by distance and the search takes O(V + E), without the heap of Dijkstra.
+pub fn zero_one_bfs<N, F, I>(start: N, mut neighbours: F) -> HashMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, Weight)>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = Deque::create();
+    queue.push_right((start, 0));
+
+    while let Some((node, distance)) = queue.pop_left() {
+        // a node can be queued again after a shorter path was found, skip the stale entry
+        if distances[&node] < distance {
+            continue;
+        }
+
+        for (next, weight) in neighbours(&node) {
+            let next_distance = match weight {
+                Weight::Zero => distance,
+                Weight::One => distance + 1,
+            };
+            if distances
+                .get(&next)
+                .is_some_and(|known| *known <= next_distance)
+            {
+                continue;
+            }
+
+            distances.insert(next.clone(), next_distance);
+            match weight {
+                Weight::Zero => queue.push_left((next, next_distance)),
+                Weight::One => queue.push_right((next, next_distance)),
+            };
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::algorithms::zero_one_bfs::{zero_one_bfs, Weight};
+
+        #[test]
+        fn free_edges_are_taken_first() {
+            // 0 -1-> 1 -1-> 3, and 0 -0-> 2 -0-> 3
+            let edges = |node: &usize| match node {
+                0 => vec![(1, Weight::One), (2, Weight::Zero)],
+                1 => vec![(3, Weight::One)],
+                2 => vec![(3, Weight::Zero)],
+                _ => vec![],
+            };
+            let distances = zero_one_bfs(0, edges);
+
+            assert_eq!(distances[&0], 0);
+            assert_eq!(distances[&1], 1);
+            assert_eq!(distances[&2], 0);
+            assert_eq!(distances[&3], 0);
+        }
+
+        #[test]
+        fn unreachable_nodes_have_no_distance() {
+            let distances = zero_one_bfs("a", |node: &&str| match *node {
+                "a" => vec![("b", Weight::One)],
+                _ => vec![],
+            });
+
+            assert_eq!(distances.len(), 2);
+            assert!(!distances.contains_key("c"));
+        }
+
+        #[test]
+        fn walls_on_a_grid_cost_one_to_break() {
+            let grid = ["..#", "#.#", "##."];
+            let cell = |(row, column): (usize, usize)| grid[row].as_bytes()[column];
+            let neighbours = |&(row, column): &(usize, usize)| {
+                let mut next = vec![];
+                if row > 0 {
+                    next.push((row - 1, column));
+                }
+                if row < 2 {
+                    next.push((row + 1, column));
+                }
+                if column > 0 {
+                    next.push((row, column - 1));
+                }
+                if column < 2 {
+                    next.push((row, column + 1));
+                }
+
+                next.into_iter().map(move |p| {
+                    let weight = if cell(p) == b'#' {
+                        Weight::One
+                    } else {
+                        Weight::Zero
+                    };
+                    (p, weight)
+                })
+            };
+            let distances = zero_one_bfs((0, 0), neighbours);
+
+            assert_eq!(distances[&(1, 1)], 0);
+            assert_eq!(distances[&(2, 2)], 1);
+        }
+    }
+
+    mod property_tests {
+        use crate::algorithms::zero_one_bfs::{zero_one_bfs, Weight};
+        use proptest::prelude::*;
+
+        /// Bellman-Ford, which is slow but obviously correct
+        fn shortest_distances(nodes: usize, edges: &[(usize, usize, bool)]) -> Vec<Option<usize>> {
+            let mut distances = vec![None; nodes];
+            distances[0] = Some(0);
+            for _ in 0..nodes {
+                for &(from, to, costly) in edges {
+                    if let Some(d) = distances[from] {
+                        let candidate = d + costly as usize;
+                        if distances[to].is_none_or(|known| candidate < known) {
+                            distances[to] = Some(candidate);
+                        }
+                    }
+                }
+            }
+
+            distances
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn distances_match_bellman_ford(edges in prop::collection::vec((0..12usize, 0..12usize, any::<bool>()), 0..40)) {
+                let expected = shortest_distances(12, &edges);
+                let distances = zero_one_bfs(0, |node: &usize| {
+                    edges
+                        .iter()
+                        .filter(|(from, _, _)| from == node)
+                        .map(|&(_, to, costly)| (to, if costly { Weight::One } else { Weight::Zero }))
+                        .collect::<Vec<_>>()
+                });
+
+                for (node, distance) in expected.into_iter().enumerate() {
+                    prop_assert_eq!(distances.get(&node).copied(), distance);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Compares the throughput of `Deque`, `ArenaDeque`, `UnrolledDeque` and `VecDeque`.
+//!
+//! Run with `cargo run --release --bin deque_benchmark`, optionally passing the number of
+//! values to use, which defaults to 1 000 000.
+
+use double_linked_list::{ArenaDeque, Deque, UnrolledDeque};
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 5;
+
+/// The operations being measured, implemented for every deque
+trait Benchmarked: Default {
+    const NAME: &'static str;
+
+    fn push_left(&mut self, x: u64);
+    fn push_right(&mut self, x: u64);
+    fn pop_left(&mut self) -> Option<u64>;
+    fn pop_right(&mut self) -> Option<u64>;
+    fn sum(&self) -> u64;
+}
+
+impl Benchmarked for Deque<u64> {
+    const NAME: &'static str = "Deque";
+
+    fn push_left(&mut self, x: u64) {
+        Deque::push_left(self, x);
+    }
+
+    fn push_right(&mut self, x: u64) {
+        Deque::push_right(self, x);
+    }
+
+    fn pop_left(&mut self) -> Option<u64> {
+        Deque::pop_left(self)
+    }
+
+    fn pop_right(&mut self) -> Option<u64> {
+        Deque::pop_right(self)
+    }
+
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+}
+
+impl Benchmarked for ArenaDeque<u64> {
+    const NAME: &'static str = "ArenaDeque";
+
+    fn push_left(&mut self, x: u64) {
+        ArenaDeque::push_left(self, x);
+    }
+
+    fn push_right(&mut self, x: u64) {
+        ArenaDeque::push_right(self, x);
+    }
+
+    fn pop_left(&mut self) -> Option<u64> {
+        ArenaDeque::pop_left(self)
+    }
+
+    fn pop_right(&mut self) -> Option<u64> {
+        ArenaDeque::pop_right(self)
+    }
+
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+}
+
+impl Benchmarked for UnrolledDeque<u64> {
+    const NAME: &'static str = "UnrolledDeque";
+
+    fn push_left(&mut self, x: u64) {
+        UnrolledDeque::push_left(self, x);
+    }
+
+    fn push_right(&mut self, x: u64) {
+        UnrolledDeque::push_right(self, x);
+    }
+
+    fn pop_left(&mut self) -> Option<u64> {
+        UnrolledDeque::pop_left(self)
+    }
+
+    fn pop_right(&mut self) -> Option<u64> {
+        UnrolledDeque::pop_right(self)
+    }
+
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+}
+
+impl Benchmarked for VecDeque<u64> {
+    const NAME: &'static str = "VecDeque";
+
+    fn push_left(&mut self, x: u64) {
+        self.push_front(x);
+    }
+
+    fn push_right(&mut self, x: u64) {
+        self.push_back(x);
+    }
+
+    fn pop_left(&mut self) -> Option<u64> {
+        self.pop_front()
+    }
+
+    fn pop_right(&mut self) -> Option<u64> {
+        self.pop_back()
+    }
+
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+}
+
+/// The fastest of several runs, which is the least disturbed by other work on the machine
+fn fastest(mut run: impl FnMut() -> Duration) -> Duration {
+    (0..ROUNDS).map(|_| run()).min().unwrap()
+}
+
+fn filled<D: Benchmarked>(n: usize) -> D {
+    let mut deque = D::default();
+    for x in 0..n as u64 {
+        deque.push_right(x);
+    }
+
+    deque
+}
+
+fn benchmark<D: Benchmarked>(n: usize) {
+    let push_pop = fastest(|| {
+        let mut deque = D::default();
+        let start = Instant::now();
+        for x in 0..n as u64 {
+            if x % 2 == 0 {
+                deque.push_left(x);
+            } else {
+                deque.push_right(x);
+            }
+        }
+        while let Some(x) = deque.pop_left() {
+            black_box(x);
+            black_box(deque.pop_right());
+        }
+
+        start.elapsed()
+    });
+
+    let deque: D = filled(n);
+    let iterate = fastest(|| {
+        let start = Instant::now();
+        black_box(black_box(&deque).sum());
+
+        start.elapsed()
+    });
+
+    println!(
+        "{:<15}{:>15.1}{:>15.1}",
+        D::NAME,
+        n as f64 / push_pop.as_secs_f64() / 1e6,
+        n as f64 / iterate.as_secs_f64() / 1e6,
+    );
+}
+
+fn main() {
+    let n = match std::env::args().nth(1) {
+        Some(arg) => arg
+            .parse()
+            .expect("the number of values must be a positive integer"),
+        None => 1_000_000,
+    };
+
+    println!("millions of values per second, for {} values", n);
+    println!("{:<15}{:>15}{:>15}", "", "push & pop", "iterate");
+    benchmark::<Deque<u64>>(n);
+    benchmark::<ArenaDeque<u64>>(n);
+    benchmark::<UnrolledDeque<u64>>(n);
+    benchmark::<VecDeque<u64>>(n);
+}
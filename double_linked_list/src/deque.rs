@@ -1,28 +1,59 @@
-pub mod clone_it;
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary_deque;
+pub mod arena;
+pub mod blocking;
+pub mod bounded;
+mod clone_it;
+mod collect;
+pub mod cursor;
 mod eq;
+mod fmt;
+pub mod handle;
+mod hash;
 pub mod item;
-mod iterator;
-pub mod macros;
-
+pub mod iterator;
+mod macros;
+mod ord;
+pub mod persistent;
+pub mod position;
+mod rearrange;
+pub mod retain;
+mod search;
+mod sort;
+mod splice;
+pub mod unrolled;
+pub mod work_stealing;
+
+use crate::deque::handle::{next_id, NodeHandle};
 use crate::deque::item::Item;
-use std::alloc::Layout;
-
-pub trait DequeTypeRequirements: PartialEq + Clone {}
-impl<T: PartialEq + Clone> DequeTypeRequirements for T {}
 
-#[derive(Debug)]
-pub struct Deque<T: DequeTypeRequirements> {
+/// A doubly linked deque with O(1) pushes and pops at both ends.
+///
+/// Popped and removed items are kept on a free list and reused by later pushes, like the
+/// capacity of a `Vec`. Their memory is returned when the deque is dropped or cleared, or
+/// by [Deque::shrink_to_fit].
+pub struct Deque<T> {
     first: *mut Item<T>,
     last: *mut Item<T>,
     len: usize,
+    // identifies the deque to the handles it hands out, see `NodeHandle`
+    id: u64,
+    // released items waiting to be reused, linked through their right pointers
+    free: *mut Item<T>,
 }
 
-impl<T: DequeTypeRequirements> Deque<T> {
+// the items are owned by the deque and only reachable through it, like the values of a `Vec`
+unsafe impl<T: Send> Send for Deque<T> {}
+unsafe impl<T: Sync> Sync for Deque<T> {}
+
+impl<T> Deque<T> {
     pub fn create() -> Deque<T> {
         Deque {
             first: std::ptr::null_mut(),
             last: std::ptr::null_mut(),
             len: 0,
+            id: next_id(),
+            free: std::ptr::null_mut(),
         }
     }
 
@@ -30,25 +61,29 @@ impl<T: DequeTypeRequirements> Deque<T> {
         self.len
     }
 
-    fn push_first(&mut self, x: T) -> &Item<T> {
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn push_first(&mut self, x: T) -> NodeHandle {
         // function can only be used when there are no other elements in the deque
         assert!(self.first.is_null());
         assert!(self.last.is_null());
 
-        let ptr = Item::create(x);
+        let ptr = self.allocate(x);
         self.first = ptr;
         self.last = ptr;
         self.len += 1;
 
-        unsafe { &*ptr }
+        self.register(ptr)
     }
 
-    pub fn push_left(&mut self, x: T) -> &Item<T> {
+    pub fn push_left(&mut self, x: T) -> NodeHandle {
         if self.first.is_null() || self.last.is_null() {
             self.push_first(x)
         } else {
             // creates the new item
-            let ptr = Item::create(x);
+            let ptr = self.allocate(x);
             let item = unsafe { &mut *ptr };
             let first = unsafe { &mut *self.first };
 
@@ -60,16 +95,16 @@ impl<T: DequeTypeRequirements> Deque<T> {
             // updates inner state
             self.len += 1;
 
-            unsafe { &*ptr }
+            self.register(ptr)
         }
     }
 
-    pub fn push_right(&mut self, x: T) -> &Item<T> {
+    pub fn push_right(&mut self, x: T) -> NodeHandle {
         if self.first.is_null() || self.last.is_null() {
             self.push_first(x)
         } else {
             // creates the new item
-            let ptr = Item::create(x);
+            let ptr = self.allocate(x);
             let item = unsafe { &mut *ptr };
             let last = unsafe { &mut *self.last };
 
@@ -81,14 +116,13 @@ impl<T: DequeTypeRequirements> Deque<T> {
             // updates inner state
             self.len += 1;
 
-            unsafe { &*ptr }
+            self.register(ptr)
         }
     }
 
     pub fn pop_left(&mut self) -> Option<T> {
         if let Some(first) = self.first() {
             let ptr = self.first;
-            let deleted = Some((*first.value()).clone());
             if first.right_ptr.is_null() {
                 // there is only one element, so the deque is now empty
                 self.first = std::ptr::null_mut();
@@ -103,15 +137,13 @@ impl<T: DequeTypeRequirements> Deque<T> {
                 self.first = second_ptr;
             }
 
-            // the first item needs to be deallocated
-            unsafe {
-                std::alloc::dealloc(ptr as *mut u8, Layout::new::<Item<T>>());
-            }
+            // the item needs to be deallocated, moving its value out to the caller
+            let deleted = unsafe { self.release(ptr) };
 
             // adjust the len
             self.len -= 1;
 
-            deleted
+            Some(deleted)
         } else {
             None
         }
@@ -120,7 +152,6 @@ impl<T: DequeTypeRequirements> Deque<T> {
     pub fn pop_right(&mut self) -> Option<T> {
         if let Some(last) = self.last() {
             let ptr = self.last;
-            let deleted = Some((*last.value()).clone());
             if last.left_ptr.is_null() {
                 // there is only one element, so the deque is now empty
                 self.first = std::ptr::null_mut();
@@ -135,15 +166,13 @@ impl<T: DequeTypeRequirements> Deque<T> {
                 self.last = previous_ptr;
             }
 
-            // the first item needs to be deallocated
-            unsafe {
-                std::alloc::dealloc(ptr as *mut u8, Layout::new::<Item<T>>());
-            }
+            // the item needs to be deallocated, moving its value out to the caller
+            let deleted = unsafe { self.release(ptr) };
 
             // adjust the len
             self.len -= 1;
 
-            deleted
+            Some(deleted)
         } else {
             None
         }
@@ -164,118 +193,220 @@ impl<T: DequeTypeRequirements> Deque<T> {
             unsafe { Some(&(*self.last)) }
         }
     }
-}
 
-impl<T: DequeTypeRequirements> Drop for Deque<T> {
-    fn drop(&mut self) {
-        // checks if there are any elements to drop
-        if !self.first.is_null() {
-            let mut nxt = self.first;
-
-            // iterates through the list using the pointers in the
-            // `right` property of each item, dropping each item
-            // in the process
-            while !nxt.is_null() {
-                let ptr = nxt;
-                let item = unsafe { &*nxt };
-                nxt = item.right_ptr;
+    /// Drops every value and frees every item, rejecting the handles handed out so far
+    pub fn clear(&mut self) {
+        *self = Deque::create();
+    }
 
-                unsafe {
-                    std::alloc::dealloc(ptr as *mut u8, Layout::new::<Item<T>>());
-                };
-            }
+    /// Frees the items kept for reuse by later pushes
+    ///
+    /// Handles handed out so far are rejected afterwards, as some of them may refer to
+    /// freed items.
+    pub fn shrink_to_fit(&mut self) {
+        if self.free.is_null() {
+            return;
         }
-    }
-}
 
-#[cfg(test)]
-pub(crate) mod arbitrary_deque {
-    use crate::deque::Deque;
-    use proptest::arbitrary::{any, Arbitrary};
-    use proptest::collection::vec;
-    use proptest::prelude::{BoxedStrategy, Just, Strategy};
-    use proptest::prop_oneof;
-    use std::fmt::Debug;
-
-    pub(crate) trait TestRequirements:
-        PartialEq + Clone + Debug + Arbitrary + 'static
-    {
+        self.deallocate_free();
+        self.id = next_id();
     }
-    impl<T: PartialEq + Clone + Debug + Arbitrary + 'static> TestRequirements for T {}
-
-    #[derive(Debug, Clone, PartialEq)]
-    pub(crate) enum ArbitraryDequeOperation<T: TestRequirements> {
-        PushLeft(T),
-        PushRight(T),
-        PopLeft,
-        PopRight,
+
+    fn deallocate_free(&mut self) {
+        // the values of released items have already been moved out
+        while !self.free.is_null() {
+            let ptr = self.free;
+            self.free = unsafe { (*ptr).right_ptr };
+
+            unsafe { Item::deallocate(ptr) };
+        }
     }
 
-    impl<T: TestRequirements> ArbitraryDequeOperation<T> {
-        pub(super) fn apply(&self, mut deque: Deque<T>) -> Deque<T> {
-            match self {
-                ArbitraryDequeOperation::PushLeft(v) => {
-                    let _ = deque.push_left(v.clone());
-                }
-                ArbitraryDequeOperation::PushRight(v) => {
-                    let _ = deque.push_right(v.clone());
-                }
-                ArbitraryDequeOperation::PopLeft => {
-                    let _ = deque.pop_left();
+    /// Finds the item at an index by walking from whichever end is closest, returning a null
+    /// pointer when the index is out of bounds
+    pub(in crate::deque) fn item_ptr_at(&self, index: usize) -> *mut Item<T> {
+        if index >= self.len {
+            return std::ptr::null_mut();
+        }
+
+        unsafe {
+            if index < self.len / 2 {
+                let mut current = self.first;
+                for _ in 0..index {
+                    current = (*current).right_ptr;
                 }
-                ArbitraryDequeOperation::PopRight => {
-                    let _ = deque.pop_right();
+                current
+            } else {
+                let mut current = self.last;
+                for _ in index..self.len - 1 {
+                    current = (*current).left_ptr;
                 }
+                current
             }
+        }
+    }
+
+    /// Links a newly created item between two neighbours, where a null pointer stands for
+    /// the respective end of the deque
+    ///
+    /// # Safety
+    ///
+    /// The neighbours must be adjacent items of this deque (or null) and the item must not be
+    /// linked into any deque.
+    pub(in crate::deque) unsafe fn link_between(
+        &mut self,
+        ptr: *mut Item<T>,
+        left_ptr: *mut Item<T>,
+        right_ptr: *mut Item<T>,
+    ) {
+        let item = &mut *ptr;
+        item.left_ptr = left_ptr;
+        item.right_ptr = right_ptr;
+
+        if left_ptr.is_null() {
+            self.first = ptr;
+        } else {
+            (*left_ptr).right_ptr = ptr;
+        }
+
+        if right_ptr.is_null() {
+            self.last = ptr;
+        } else {
+            (*right_ptr).left_ptr = ptr;
+        }
+
+        self.len += 1;
+    }
+
+    /// Unlinks an item from its neighbours, without deallocating it
+    ///
+    /// # Safety
+    ///
+    /// The item must be part of this deque.
+    pub(in crate::deque) unsafe fn unlink(&mut self, ptr: *mut Item<T>) {
+        let item = &mut *ptr;
+
+        if item.left_ptr.is_null() {
+            self.first = item.right_ptr;
+        } else {
+            (*item.left_ptr).right_ptr = item.right_ptr;
+        }
 
-            deque
+        if item.right_ptr.is_null() {
+            self.last = item.left_ptr;
+        } else {
+            (*item.right_ptr).left_ptr = item.left_ptr;
         }
+
+        item.left_ptr = std::ptr::null_mut();
+        item.right_ptr = std::ptr::null_mut();
+        self.len -= 1;
     }
 
-    impl<T: TestRequirements> Arbitrary for ArbitraryDequeOperation<T> {
-        type Parameters = ();
+    /// Moves all items of another deque between two neighbours in O(1)
+    ///
+    /// # Safety
+    ///
+    /// The neighbours must be adjacent items of this deque (or null).
+    pub(in crate::deque) unsafe fn splice_between(
+        &mut self,
+        mut other: Deque<T>,
+        left_ptr: *mut Item<T>,
+        right_ptr: *mut Item<T>,
+    ) {
+        if other.is_empty() {
+            return;
+        }
 
-        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
-            (any::<T>())
-                .prop_flat_map(|value| {
-                    prop_oneof![
-                        Just(ArbitraryDequeOperation::PushLeft(value.clone())),
-                        Just(ArbitraryDequeOperation::PushRight(value.clone())),
-                        Just(ArbitraryDequeOperation::PopLeft),
-                        Just(ArbitraryDequeOperation::PopRight)
-                    ]
-                })
-                .boxed()
+        (*other.first).left_ptr = left_ptr;
+        (*other.last).right_ptr = right_ptr;
+
+        if left_ptr.is_null() {
+            self.first = other.first;
+        } else {
+            (*left_ptr).right_ptr = other.first;
         }
 
-        type Strategy = BoxedStrategy<ArbitraryDequeOperation<T>>;
+        if right_ptr.is_null() {
+            self.last = other.last;
+        } else {
+            (*right_ptr).left_ptr = other.last;
+        }
+
+        self.len += other.len;
+
+        // the items are owned by this deque now, so the other deque must not drop them
+        other.first = std::ptr::null_mut();
+        other.last = std::ptr::null_mut();
+        other.len = 0;
     }
 
-    #[allow(unused_mut)]
-    fn apply_operations<T: TestRequirements>(
-        mut input: (Deque<T>, Vec<ArbitraryDequeOperation<T>>),
+    /// Detaches a run of `len` items, from `first_ptr` up to and including `last_ptr`, into a
+    /// deque of its own in O(1)
+    ///
+    /// The handles of this deque are invalidated, as some of them now refer to items it no
+    /// longer owns.
+    ///
+    /// # Safety
+    ///
+    /// Both items must be part of this deque, with `first_ptr` at or left of `last_ptr` and
+    /// exactly `len` items between them (inclusive).
+    pub(in crate::deque) unsafe fn detach(
+        &mut self,
+        first_ptr: *mut Item<T>,
+        last_ptr: *mut Item<T>,
+        len: usize,
     ) -> Deque<T> {
-        let mut data = input.0;
-        for op in input.1 {
-            data = op.apply(data);
+        let left_ptr = (*first_ptr).left_ptr;
+        let right_ptr = (*last_ptr).right_ptr;
+
+        if left_ptr.is_null() {
+            self.first = right_ptr;
+        } else {
+            (*left_ptr).right_ptr = right_ptr;
+        }
+
+        if right_ptr.is_null() {
+            self.last = left_ptr;
+        } else {
+            (*right_ptr).left_ptr = left_ptr;
         }
 
-        data
+        (*first_ptr).left_ptr = std::ptr::null_mut();
+        (*last_ptr).right_ptr = std::ptr::null_mut();
+        self.len -= len;
+
+        self.id = next_id();
+
+        Deque {
+            first: first_ptr,
+            last: last_ptr,
+            len,
+            id: next_id(),
+            free: std::ptr::null_mut(),
+        }
     }
+}
 
-    impl<T: TestRequirements> Arbitrary for Deque<T> {
-        type Parameters = ();
+impl<T> Drop for Deque<T> {
+    fn drop(&mut self) {
+        // checks if there are any elements to drop
+        if !self.first.is_null() {
+            let mut nxt = self.first;
+
+            // iterates through the list using the pointers in the
+            // `right` property of each item, dropping each item
+            // and its value in the process
+            while !nxt.is_null() {
+                let ptr = nxt;
+                let item = unsafe { &*nxt };
+                nxt = item.right_ptr;
 
-        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
-            (
-                Just(Deque::<T>::create()),
-                vec(any::<ArbitraryDequeOperation<T>>(), 0..100),
-            )
-                .prop_map(apply_operations)
-                .boxed()
+                drop(unsafe { Item::destroy(ptr) });
+            }
         }
 
-        type Strategy = BoxedStrategy<Deque<T>>;
+        self.deallocate_free();
     }
 }
 
@@ -392,12 +523,110 @@ mod tests {
         assert_eq!(data, deque![]);
     }
 
+    #[test]
+    fn popping_moves_owned_values_out() {
+        let mut data = Deque::create();
+        data.push_right(String::from("left"));
+        data.push_right(String::from("middle"));
+        data.push_right(String::from("right"));
+
+        assert_eq!(data.pop_left(), Some(String::from("left")));
+        assert_eq!(data.pop_right(), Some(String::from("right")));
+        assert_eq!(data.pop_left(), Some(String::from("middle")));
+        assert_eq!(data.pop_left(), None);
+    }
+
+    #[test]
+    fn dropping_drops_the_remaining_values() {
+        let mut data = Deque::create();
+        data.push_left(Box::new(1));
+        data.push_right(Box::new(2));
+        data.push_right(Box::new(3));
+
+        assert_eq!(data.pop_right(), Some(Box::new(3)));
+    }
+
+    #[test]
+    fn values_are_dropped_exactly_once() {
+        let counter = std::rc::Rc::new(());
+        {
+            let mut data = Deque::create();
+            for _ in 0..5 {
+                data.push_right(std::rc::Rc::clone(&counter));
+            }
+            assert_eq!(std::rc::Rc::strong_count(&counter), 6);
+
+            drop(data.pop_left());
+            drop(data.pop_right());
+            assert_eq!(std::rc::Rc::strong_count(&counter), 4);
+        }
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn values_do_not_need_to_be_cloneable_or_comparable() {
+        struct Opaque(String);
+
+        let mut data = Deque::create();
+        data.push_left(Opaque(String::from("a")));
+        data.push_right(Opaque(String::from("b")));
+
+        assert_eq!(data.len(), 2);
+        assert_eq!(data.pop_right().map(|x| x.0), Some(String::from("b")));
+        assert!(!data.is_empty());
+    }
+
+    #[test]
+    fn shrinking_frees_the_released_items_and_rejects_handles() {
+        let mut data = Deque::create();
+        let kept = data.push_right(1);
+        for x in 2..10 {
+            data.push_right(x);
+        }
+        for _ in 2..10 {
+            data.pop_right();
+        }
+        assert!(!data.free.is_null());
+
+        data.shrink_to_fit();
+        assert!(data.free.is_null());
+        assert_eq!(data.get(kept), None);
+        assert_eq!(data, deque![1]);
+
+        let pushed = data.push_right(2);
+        assert_eq!(data.get(pushed), Some(&2));
+    }
+
+    #[test]
+    fn shrinking_without_released_items_keeps_handles() {
+        let mut data = Deque::create();
+        let kept = data.push_right(1);
+        data.shrink_to_fit();
+
+        assert_eq!(data.get(kept), Some(&1));
+    }
+
+    #[test]
+    fn clearing_drops_every_value_and_item() {
+        let counter = std::rc::Rc::new(());
+        let mut data = Deque::create();
+        let handle = data.push_right(std::rc::Rc::clone(&counter));
+        data.push_right(std::rc::Rc::clone(&counter));
+        data.pop_left();
+        data.clear();
+
+        assert!(data.is_empty());
+        assert!(data.free.is_null());
+        assert_eq!(data.get(handle), None);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
     #[test]
     fn push_left_once() {
         let mut data = Deque::create();
         let it = data.push_left(1);
 
-        assert_eq!(it.value(), &1);
+        assert_eq!(data.get(it), Some(&1));
 
         unsafe {
             assert_eq!(1, (*data.first).value);
@@ -0,0 +1,257 @@
+//! Random deques and operations on them for property tests, enabled by the `proptest`
+//! feature.
+//!
+//! An arbitrary [Deque] is built by applying a random sequence of
+//! [ArbitraryDequeOperation]s to an empty deque, so it is shaped by the same code paths
+//! that shape deques in practice.
+
+use crate::deque::Deque;
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::collection::vec;
+use proptest::prelude::{BoxedStrategy, Just, Strategy};
+use proptest::prop_oneof;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+pub trait TestRequirements: PartialEq + Clone + Debug + Arbitrary + 'static {}
+impl<T: PartialEq + Clone + Debug + Arbitrary + 'static> TestRequirements for T {}
+
+/// An operation on a deque.
+///
+/// Positions are taken modulo the length of the deque plus one, where a position equal
+/// to the length stands for the ghost position of a cursor, so that every operation
+/// applies to a deque of any length.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArbitraryDequeOperation<T: TestRequirements> {
+    PushLeft(T),
+    PushRight(T),
+    PopLeft,
+    PopRight,
+    /// Inserts with [CursorMut::insert_after](crate::deque::cursor::CursorMut::insert_after)
+    InsertAfter(usize, T),
+    /// Inserts with [CursorMut::insert_before](crate::deque::cursor::CursorMut::insert_before)
+    InsertBefore(usize, T),
+    /// Removes with [CursorMut::remove_current](crate::deque::cursor::CursorMut::remove_current)
+    RemoveCurrent(usize),
+    /// Drops the values split off with [Deque::split_off]
+    SplitOff(usize),
+    /// Appends a deque with these values with [Deque::append]
+    Append(Vec<T>),
+    /// Prepends a deque with these values with [Deque::prepend]
+    Prepend(Vec<T>),
+}
+
+impl<T: TestRequirements> ArbitraryDequeOperation<T> {
+    pub fn apply(&self, mut deque: Deque<T>) -> Deque<T> {
+        let len = deque.len();
+        match self {
+            ArbitraryDequeOperation::PushLeft(v) => {
+                let _ = deque.push_left(v.clone());
+            }
+            ArbitraryDequeOperation::PushRight(v) => {
+                let _ = deque.push_right(v.clone());
+            }
+            ArbitraryDequeOperation::PopLeft => {
+                let _ = deque.pop_left();
+            }
+            ArbitraryDequeOperation::PopRight => {
+                let _ = deque.pop_right();
+            }
+            ArbitraryDequeOperation::InsertAfter(position, v) => {
+                let mut cursor = deque.cursor_first_mut();
+                for _ in 0..position % (len + 1) {
+                    cursor.move_next();
+                }
+                cursor.insert_after(v.clone());
+            }
+            ArbitraryDequeOperation::InsertBefore(position, v) => {
+                let mut cursor = deque.cursor_first_mut();
+                for _ in 0..position % (len + 1) {
+                    cursor.move_next();
+                }
+                cursor.insert_before(v.clone());
+            }
+            ArbitraryDequeOperation::RemoveCurrent(position) => {
+                let mut cursor = deque.cursor_first_mut();
+                for _ in 0..position % (len + 1) {
+                    cursor.move_next();
+                }
+                let _ = cursor.remove_current();
+            }
+            ArbitraryDequeOperation::SplitOff(position) => {
+                let _ = deque.split_off(position % (len + 1));
+            }
+            ArbitraryDequeOperation::Append(values) => {
+                deque.append(&mut values.iter().cloned().collect());
+            }
+            ArbitraryDequeOperation::Prepend(values) => {
+                deque.prepend(&mut values.iter().cloned().collect());
+            }
+        }
+
+        deque
+    }
+
+    /// Applies the operation to a `VecDeque`, which serves as the reference for a deque
+    pub fn apply_to_model(&self, model: &mut VecDeque<T>) {
+        let len = model.len();
+        match self {
+            ArbitraryDequeOperation::PushLeft(v) => model.push_front(v.clone()),
+            ArbitraryDequeOperation::PushRight(v) => model.push_back(v.clone()),
+            ArbitraryDequeOperation::PopLeft => {
+                let _ = model.pop_front();
+            }
+            ArbitraryDequeOperation::PopRight => {
+                let _ = model.pop_back();
+            }
+            ArbitraryDequeOperation::InsertAfter(position, v) => {
+                // after the ghost position means at the start
+                let at = position % (len + 1);
+                model.insert(if at == len { 0 } else { at + 1 }, v.clone());
+            }
+            ArbitraryDequeOperation::InsertBefore(position, v) => {
+                // before the ghost position means at the end
+                model.insert(position % (len + 1), v.clone());
+            }
+            ArbitraryDequeOperation::RemoveCurrent(position) => {
+                let _ = model.remove(position % (len + 1));
+            }
+            ArbitraryDequeOperation::SplitOff(position) => model.truncate(position % (len + 1)),
+            ArbitraryDequeOperation::Append(values) => model.extend(values.iter().cloned()),
+            ArbitraryDequeOperation::Prepend(values) => {
+                for v in values.iter().rev() {
+                    model.push_front(v.clone());
+                }
+            }
+        }
+    }
+}
+
+impl<T: TestRequirements> Arbitrary for ArbitraryDequeOperation<T> {
+    type Parameters = ();
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            3 => any::<T>().prop_map(ArbitraryDequeOperation::PushLeft),
+            3 => any::<T>().prop_map(ArbitraryDequeOperation::PushRight),
+            2 => Just(ArbitraryDequeOperation::PopLeft),
+            2 => Just(ArbitraryDequeOperation::PopRight),
+            2 => (any::<usize>(), any::<T>())
+                .prop_map(|(position, v)| ArbitraryDequeOperation::InsertAfter(position, v)),
+            2 => (any::<usize>(), any::<T>())
+                .prop_map(|(position, v)| ArbitraryDequeOperation::InsertBefore(position, v)),
+            1 => any::<usize>().prop_map(ArbitraryDequeOperation::RemoveCurrent),
+            1 => any::<usize>().prop_map(ArbitraryDequeOperation::SplitOff),
+            1 => vec(any::<T>(), 0..8).prop_map(ArbitraryDequeOperation::Append),
+            1 => vec(any::<T>(), 0..8).prop_map(ArbitraryDequeOperation::Prepend),
+        ]
+        .boxed()
+    }
+
+    type Strategy = BoxedStrategy<ArbitraryDequeOperation<T>>;
+}
+
+fn apply_operations<T: TestRequirements>(
+    input: (Deque<T>, Vec<ArbitraryDequeOperation<T>>),
+) -> Deque<T> {
+    let (mut data, operations) = input;
+    for op in operations {
+        data = op.apply(data);
+    }
+
+    data
+}
+
+impl<T: TestRequirements> Arbitrary for Deque<T> {
+    type Parameters = ();
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            Just(Deque::<T>::create()),
+            vec(any::<ArbitraryDequeOperation<T>>(), 0..100),
+        )
+            .prop_map(apply_operations)
+            .boxed()
+    }
+
+    type Strategy = BoxedStrategy<Deque<T>>;
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::arbitrary_deque::ArbitraryDequeOperation;
+        use std::collections::VecDeque;
+
+        #[test]
+        fn cursor_operations_at_the_ghost_position_use_the_ends() {
+            let operations = [
+                ArbitraryDequeOperation::InsertAfter(3, 0),
+                ArbitraryDequeOperation::InsertBefore(4, 4),
+                ArbitraryDequeOperation::RemoveCurrent(5),
+            ];
+            let mut data = deque![1, 2, 3];
+            let mut model = VecDeque::from([1, 2, 3]);
+            for (operation, position) in operations.iter().zip([3, 4, 5]) {
+                assert_eq!(data.len(), position);
+                data = operation.apply(data);
+                operation.apply_to_model(&mut model);
+            }
+
+            assert_eq!(
+                data.iter().copied().collect::<Vec<_>>(),
+                vec![0, 1, 2, 3, 4]
+            );
+            assert!(data.iter().eq(model.iter()));
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::arbitrary_deque::ArbitraryDequeOperation;
+        use crate::deque::Deque;
+        use proptest::prelude::*;
+        use std::collections::VecDeque;
+
+        /// Walks the links of the items, rather than trusting the iterators and `len`
+        fn linked_values(deque: &Deque<i16>) -> (Vec<i16>, Vec<i16>) {
+            let mut rightwards = vec![];
+            let mut item = deque.first();
+            while let Some(current) = item {
+                rightwards.push(*current.value());
+                item = current.right();
+            }
+
+            let mut leftwards = vec![];
+            let mut item = deque.last();
+            while let Some(current) = item {
+                leftwards.push(*current.value());
+                item = current.left();
+            }
+            leftwards.reverse();
+
+            (rightwards, leftwards)
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn every_step_matches_a_vec_deque(operations: Vec<ArbitraryDequeOperation<i16>>) {
+                let mut data = Deque::create();
+                let mut model = VecDeque::new();
+                for operation in operations {
+                    data = operation.apply(data);
+                    operation.apply_to_model(&mut model);
+
+                    let expected: Vec<i16> = model.iter().copied().collect();
+                    prop_assert_eq!(data.len(), model.len());
+                    prop_assert_eq!(data.is_empty(), model.is_empty());
+                    prop_assert_eq!(data.iter().copied().collect::<Vec<_>>(), expected.clone());
+                    prop_assert!(data.iter().rev().eq(model.iter().rev()));
+                    prop_assert_eq!(linked_values(&data), (expected.clone(), expected));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,727 @@
+pub mod cursor;
+pub mod iterator;
+mod rearrange;
+pub mod retain;
+mod search;
+mod sort;
+mod splice;
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+
+/// The index standing in for a missing neighbour or an empty free list
+const NIL: usize = usize::MAX;
+
+/// A double ended queue whose items live in a single buffer, linked by their indices.
+///
+/// Removed items are put on a free list and reused by later pushes, so once the buffer
+/// has grown to the largest length the deque reaches, pushing and popping no longer
+/// allocate. [ArenaDeque::compact] moves the items back into their logical order.
+///
+/// It offers the same operations as [crate::Deque]. Those relinking items within the deque,
+/// like sorting or rotating, work the same, but moving items to another deque, like
+/// appending or splitting off, moves their values into the other buffer, in O(m) for the
+/// m values moved.
+pub struct ArenaDeque<T> {
+    slots: Vec<Slot<T>>,
+    first: usize,
+    last: usize,
+    len: usize,
+    // the first slot of the free list, which continues through the `right` links
+    free: usize,
+    // above the generation of every slot, including those dropped by `compact`, so a slot
+    // added later does not match a handle of one of them; at 64 bits, generations only
+    // increase by one per removal and cannot realistically wrap around
+    next_generation: u64,
+}
+
+struct Slot<T> {
+    // `None` while the slot is on the free list
+    value: Option<T>,
+    left: usize,
+    right: usize,
+    // increased whenever the slot is freed, so handles to earlier values are rejected
+    generation: u64,
+}
+
+/// A copyable reference to an item of an [ArenaDeque], returned when pushing a value.
+///
+/// A handle is rejected once its item is removed, even when the slot is reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArenaHandle {
+    index: usize,
+    generation: u64,
+}
+
+pub(in crate::deque) mod sealed {
+    use crate::deque::arena::ArenaDeque;
+
+    pub trait Locate {
+        /// Finds the slot of the item in the deque, if it is there
+        fn locate<T>(&self, deque: &ArenaDeque<T>) -> Option<usize>;
+    }
+}
+
+/// Identifies an item of an [ArenaDeque], either by index or by [ArenaHandle]
+pub trait ArenaPosition: sealed::Locate {}
+
+impl sealed::Locate for usize {
+    fn locate<T>(&self, deque: &ArenaDeque<T>) -> Option<usize> {
+        deque.slot_at(*self)
+    }
+}
+
+impl ArenaPosition for usize {}
+
+impl sealed::Locate for ArenaHandle {
+    fn locate<T>(&self, deque: &ArenaDeque<T>) -> Option<usize> {
+        let slot = deque.slots.get(self.index)?;
+        if slot.value.is_some() && slot.generation == self.generation {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+}
+
+impl ArenaPosition for ArenaHandle {}
+
+impl<T> ArenaDeque<T> {
+    pub fn create() -> ArenaDeque<T> {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a deque that can hold `capacity` items before it allocates
+    pub fn with_capacity(capacity: usize) -> ArenaDeque<T> {
+        ArenaDeque {
+            slots: Vec::with_capacity(capacity),
+            first: NIL,
+            last: NIL,
+            len: 0,
+            free: NIL,
+            next_generation: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of items the deque can hold before it allocates
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    pub fn push_left(&mut self, x: T) -> ArenaHandle {
+        let index = self.occupy(x);
+        self.link_between(index, NIL, self.first);
+
+        self.handle(index)
+    }
+
+    pub fn push_right(&mut self, x: T) -> ArenaHandle {
+        let index = self.occupy(x);
+        self.link_between(index, self.last, NIL);
+
+        self.handle(index)
+    }
+
+    pub fn pop_left(&mut self) -> Option<T> {
+        if self.first == NIL {
+            return None;
+        }
+
+        Some(self.take(self.first))
+    }
+
+    pub fn pop_right(&mut self) -> Option<T> {
+        if self.last == NIL {
+            return None;
+        }
+
+        Some(self.take(self.last))
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.value(self.first)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.value(self.last)
+    }
+
+    pub fn get<P: ArenaPosition>(&self, position: P) -> Option<&T> {
+        self.value(position.locate(self)?)
+    }
+
+    pub fn get_mut<P: ArenaPosition>(&mut self, position: P) -> Option<&mut T> {
+        let index = position.locate(self)?;
+        self.value_mut(index)
+    }
+
+    /// Removes an item, returning its value
+    pub fn remove<P: ArenaPosition>(&mut self, position: P) -> Option<T> {
+        let index = position.locate(self)?;
+        Some(self.take(index))
+    }
+
+    /// Inserts a value so that it ends up at the given index, shifting later items right
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is larger than the length of the deque.
+    pub fn insert(&mut self, index: usize, x: T) -> ArenaHandle {
+        assert!(
+            index <= self.len,
+            "Cannot insert at {} in a deque of length {}",
+            index,
+            self.len
+        );
+
+        let (left, right) = match self.slot_at(index) {
+            Some(right) => (self.slots[right].left, right),
+            None => (self.last, NIL),
+        };
+        let created = self.occupy(x);
+        self.link_between(created, left, right);
+
+        self.handle(created)
+    }
+
+    /// Moves an item to the front, returning whether the handle was valid
+    pub fn move_to_front(&mut self, handle: ArenaHandle) -> bool {
+        match sealed::Locate::locate(&handle, self) {
+            Some(index) => {
+                self.unlink(index);
+                self.link_between(index, NIL, self.first);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves an item to the back, returning whether the handle was valid
+    pub fn move_to_back(&mut self, handle: ArenaHandle) -> bool {
+        match sealed::Locate::locate(&handle, self) {
+            Some(index) => {
+                self.unlink(index);
+                self.link_between(index, self.last, NIL);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts a value right of an item, or gives the value back when the handle is invalid
+    pub fn insert_after(&mut self, handle: ArenaHandle, x: T) -> Result<ArenaHandle, T> {
+        match sealed::Locate::locate(&handle, self) {
+            Some(index) => {
+                let created = self.occupy(x);
+                self.link_between(created, index, self.slots[index].right);
+
+                Ok(self.handle(created))
+            }
+            None => Err(x),
+        }
+    }
+
+    /// Removes every item, keeping the buffer for later pushes
+    pub fn clear(&mut self) {
+        while self.pop_right().is_some() {}
+    }
+
+    /// Moves the items to the front of the buffer in their logical order and drops the
+    /// free list, so iterating walks the buffer sequentially.
+    ///
+    /// Items move to other slots, so handles handed out before compacting are rejected.
+    pub fn compact(&mut self) {
+        // a generation none of the existing handles can have
+        let generation = self
+            .slots
+            .iter()
+            .map(|slot| slot.generation + 1)
+            .fold(self.next_generation, u64::max);
+        self.next_generation = generation + 1;
+
+        let mut old = std::mem::take(&mut self.slots);
+        self.slots.reserve_exact(old.capacity());
+
+        let mut current = self.first;
+        while current != NIL {
+            let slot = &mut old[current];
+            current = slot.right;
+
+            let index = self.slots.len();
+            self.slots.push(Slot {
+                value: slot.value.take(),
+                left: index.checked_sub(1).unwrap_or(NIL),
+                right: index + 1,
+                generation,
+            });
+        }
+
+        self.free = NIL;
+        if let Some(last) = self.slots.last_mut() {
+            last.right = NIL;
+            self.first = 0;
+            self.last = self.slots.len() - 1;
+        }
+    }
+
+    /// Puts a value in a free slot, growing the buffer only when there is none
+    fn occupy(&mut self, x: T) -> usize {
+        self.len += 1;
+
+        if self.free == NIL {
+            self.slots.push(Slot {
+                value: Some(x),
+                left: NIL,
+                right: NIL,
+                generation: self.next_generation,
+            });
+            return self.slots.len() - 1;
+        }
+
+        let index = self.free;
+        let slot = &mut self.slots[index];
+        self.free = slot.right;
+        slot.value = Some(x);
+
+        index
+    }
+
+    /// Unlinks an item and puts its slot on the free list, moving its value out
+    fn take(&mut self, index: usize) -> T {
+        self.unlink(index);
+        self.len -= 1;
+
+        let slot = &mut self.slots[index];
+        slot.generation += 1;
+        slot.right = self.free;
+        self.free = index;
+
+        slot.value.take().unwrap()
+    }
+
+    fn link_between(&mut self, index: usize, left: usize, right: usize) {
+        self.slots[index].left = left;
+        self.slots[index].right = right;
+
+        if left == NIL {
+            self.first = index;
+        } else {
+            self.slots[left].right = index;
+        }
+        if right == NIL {
+            self.last = index;
+        } else {
+            self.slots[right].left = index;
+        }
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let Slot { left, right, .. } = self.slots[index];
+
+        if left == NIL {
+            self.first = right;
+        } else {
+            self.slots[left].right = right;
+        }
+        if right == NIL {
+            self.last = left;
+        } else {
+            self.slots[right].left = left;
+        }
+    }
+
+    /// The slot of the item at an index, walking from whichever end is closest
+    fn slot_at(&self, index: usize) -> Option<usize> {
+        if index >= self.len {
+            return None;
+        }
+
+        let mut current;
+        if index < self.len / 2 {
+            current = self.first;
+            for _ in 0..index {
+                current = self.slots[current].right;
+            }
+        } else {
+            current = self.last;
+            for _ in index + 1..self.len {
+                current = self.slots[current].left;
+            }
+        }
+
+        Some(current)
+    }
+
+    fn value(&self, index: usize) -> Option<&T> {
+        self.slots.get(index)?.value.as_ref()
+    }
+
+    fn value_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.slots.get_mut(index)?.value.as_mut()
+    }
+
+    fn handle(&self, index: usize) -> ArenaHandle {
+        ArenaHandle {
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+}
+
+impl<T> Default for ArenaDeque<T> {
+    fn default() -> Self {
+        ArenaDeque::create()
+    }
+}
+
+impl<T: Clone> Clone for ArenaDeque<T> {
+    fn clone(&self) -> Self {
+        let mut clone = ArenaDeque::with_capacity(self.len);
+        clone.extend(self.iter().cloned());
+
+        clone
+    }
+}
+
+impl<T: Debug> Debug for ArenaDeque<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArenaDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArenaDeque<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArenaDeque<T> {
+    /// Compares the values lexicographically, from left to right
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArenaDeque<T> {
+    /// Compares the values lexicographically, from left to right
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArenaDeque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+impl<T> Extend<T> for ArenaDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push_right(x);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for ArenaDeque<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> FromIterator<T> for ArenaDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = ArenaDeque::create();
+        deque.extend(iter);
+
+        deque
+    }
+}
+
+impl<T> From<Vec<T>> for ArenaDeque<T> {
+    fn from(values: Vec<T>) -> Self {
+        let mut deque = ArenaDeque::with_capacity(values.len());
+        deque.extend(values);
+
+        deque
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArenaDeque<T> {
+    fn from(values: [T; N]) -> Self {
+        let mut deque = ArenaDeque::with_capacity(N);
+        deque.extend(values);
+
+        deque
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::values;
+        use std::rc::Rc;
+
+        #[test]
+        fn values_are_pushed_and_popped_at_both_ends() {
+            let mut data = ArenaDeque::create();
+            data.push_right(2);
+            data.push_left(1);
+            data.push_right(3);
+
+            assert_eq!(data.len(), 3);
+            assert_eq!(data.first(), Some(&1));
+            assert_eq!(data.last(), Some(&3));
+            assert_eq!(data.pop_left(), Some(1));
+            assert_eq!(data.pop_right(), Some(3));
+            assert_eq!(data.pop_right(), Some(2));
+            assert_eq!(data.pop_left(), None);
+            assert!(data.is_empty());
+        }
+
+        #[test]
+        fn freed_slots_are_reused_without_growing() {
+            let mut data = ArenaDeque::with_capacity(4);
+            for x in 0..4 {
+                data.push_right(x);
+            }
+            let capacity = data.capacity();
+
+            for x in 4..1000 {
+                data.pop_left();
+                data.push_right(x);
+            }
+
+            assert_eq!(data.capacity(), capacity);
+            assert_eq!(values(&data), vec![996, 997, 998, 999]);
+        }
+
+        #[test]
+        fn the_capacity_counts_occupied_slots() {
+            let mut data = ArenaDeque::with_capacity(10);
+            data.push_right(1);
+
+            assert_eq!(data.capacity(), 10);
+        }
+
+        #[test]
+        fn handles_of_removed_values_are_rejected() {
+            let mut data = ArenaDeque::create();
+            let one = data.push_right(1);
+            assert_eq!(data.remove(one), Some(1));
+
+            // the new value reuses the slot of the removed one
+            let two = data.push_right(2);
+            assert_eq!(data.get(one), None);
+            assert_eq!(data.get_mut(one), None);
+            assert_eq!(data.get(two), Some(&2));
+        }
+
+        #[test]
+        fn values_can_be_read_and_inserted_by_index() {
+            let mut data: ArenaDeque<usize> = vec![1, 3].into();
+            data.insert(1, 2);
+            data.insert(0, 0);
+            let handle = data.insert(4, 4);
+            *data.get_mut(2).unwrap() *= 10;
+
+            assert_eq!(values(&data), vec![0, 1, 20, 3, 4]);
+            assert_eq!(data.get(4), data.get(handle));
+            assert_eq!(data.get(5), None);
+            assert_eq!(data.remove(1), Some(1));
+        }
+
+        #[test]
+        fn items_can_be_moved_to_either_end() {
+            let mut data = ArenaDeque::create();
+            data.push_right(1);
+            let two = data.push_right(2);
+            data.push_right(3);
+
+            assert!(data.move_to_front(two));
+            assert_eq!(values(&data), vec![2, 1, 3]);
+            assert!(data.move_to_back(two));
+            assert_eq!(values(&data), vec![1, 3, 2]);
+
+            data.remove(two);
+            assert!(!data.move_to_back(two));
+        }
+
+        #[test]
+        fn values_can_be_inserted_after_a_handle() {
+            let mut data = ArenaDeque::create();
+            let one = data.push_right(1);
+            data.push_right(3);
+            let two = data.insert_after(one, 2).unwrap();
+
+            assert_eq!(data.get(two), Some(&2));
+            assert_eq!(values(&data), vec![1, 2, 3]);
+
+            data.remove(one);
+            assert_eq!(data.insert_after(one, 4), Err(4));
+        }
+
+        #[test]
+        fn compacting_restores_the_physical_order() {
+            let mut data = ArenaDeque::create();
+            for x in 0..10 {
+                data.push_left(x);
+            }
+            let handle = data.push_right(10);
+            data.retain(|x| x % 2 == 0);
+            data.compact();
+
+            assert_eq!(values(&data), vec![8, 6, 4, 2, 0, 10]);
+            assert_eq!(data.get(handle), None);
+
+            let physical: Vec<_> = data.slots.iter().map(|slot| slot.value.unwrap()).collect();
+            assert_eq!(physical, values(&data));
+            assert_eq!(
+                data.iter().rev().copied().collect::<Vec<_>>(),
+                vec![10, 0, 2, 4, 6, 8]
+            );
+        }
+
+        #[test]
+        fn handles_stay_rejected_after_compacting_and_pushing() {
+            let mut data = ArenaDeque::create();
+            data.push_right("a");
+            data.push_right("b");
+            let c = data.push_right("c");
+            data.pop_left();
+            data.compact();
+            assert_eq!(data.get(c), None);
+
+            let d = data.push_right("d");
+            assert_eq!(data.get(c), None);
+            assert_eq!(data.get(d), Some(&"d"));
+
+            data.compact();
+            let e = data.push_right("e");
+            assert_eq!(data.get(d), None);
+            assert_eq!(data.get(e), Some(&"e"));
+        }
+
+        #[test]
+        fn reversing_swaps_the_ends() {
+            let mut data: ArenaDeque<usize> = (1..=3).collect();
+            data.reverse();
+
+            assert_eq!(values(&data), vec![3, 2, 1]);
+            assert_eq!(data.first(), Some(&3));
+            assert!(data.contains(&2));
+        }
+
+        #[test]
+        fn values_are_dropped_once() {
+            let counter = Rc::new(());
+            {
+                let mut data = ArenaDeque::create();
+                for _ in 0..10 {
+                    data.push_left(Rc::clone(&counter));
+                }
+                data.pop_right();
+                data.clear();
+                data.push_left(Rc::clone(&counter));
+            }
+
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+
+        #[test]
+        fn standard_traits_work_on_the_values() {
+            let data: ArenaDeque<String> = vec![String::from("a"), String::from("b")].into();
+
+            assert_eq!(data.clone(), data);
+            let mut numbers: ArenaDeque<usize> = [1].into();
+            numbers.extend(&[2, 3]);
+            assert_eq!(values(&numbers), vec![1, 2, 3]);
+            assert!(data < [String::from("b")].into());
+            assert!(ArenaDeque::<f64>::from([1.0, f64::NAN])
+                .partial_cmp(&[1.0, 2.0].into())
+                .is_none());
+            assert_eq!(format!("{:?}", data), "[\"a\", \"b\"]");
+            assert_eq!(ArenaDeque::<usize>::default(), ArenaDeque::create());
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::arena::ArenaDeque;
+        use proptest::prelude::*;
+        use std::collections::VecDeque;
+
+        #[derive(Debug, Clone)]
+        enum Operation {
+            PushLeft(u8),
+            PushRight(u8),
+            PopLeft,
+            PopRight,
+            Insert(prop::sample::Index, u8),
+            Remove(prop::sample::Index),
+            Compact,
+        }
+
+        fn operation() -> impl Strategy<Value = Operation> {
+            prop_oneof![
+                any::<u8>().prop_map(Operation::PushLeft),
+                any::<u8>().prop_map(Operation::PushRight),
+                Just(Operation::PopLeft),
+                Just(Operation::PopRight),
+                (any::<prop::sample::Index>(), any::<u8>())
+                    .prop_map(|(i, x)| Operation::Insert(i, x)),
+                any::<prop::sample::Index>().prop_map(Operation::Remove),
+                Just(Operation::Compact),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn operations_match_a_vec_deque(operations in prop::collection::vec(operation(), 0..200)) {
+                let mut data = ArenaDeque::create();
+                let mut expected = VecDeque::new();
+
+                for operation in operations {
+                    match operation {
+                        Operation::PushLeft(x) => { data.push_left(x); expected.push_front(x); }
+                        Operation::PushRight(x) => { data.push_right(x); expected.push_back(x); }
+                        Operation::PopLeft => prop_assert_eq!(data.pop_left(), expected.pop_front()),
+                        Operation::PopRight => prop_assert_eq!(data.pop_right(), expected.pop_back()),
+                        Operation::Insert(index, x) => {
+                            let index = index.index(expected.len() + 1);
+                            data.insert(index, x);
+                            expected.insert(index, x);
+                        }
+                        Operation::Remove(index) => {
+                            if !expected.is_empty() {
+                                let index = index.index(expected.len());
+                                prop_assert_eq!(data.remove(index), expected.remove(index));
+                            }
+                        }
+                        Operation::Compact => data.compact(),
+                    }
+                }
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(data.iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>());
+                prop_assert_eq!(data.iter().rev().collect::<Vec<_>>(), expected.iter().rev().collect::<Vec<_>>());
+            }
+        }
+    }
+}
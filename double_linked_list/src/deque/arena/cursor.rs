@@ -0,0 +1,497 @@
+use crate::deque::arena::{ArenaDeque, NIL};
+
+/// A read-only position in an [ArenaDeque] that can move in both directions.
+///
+/// Like [crate::Cursor], it can point at a "ghost" position between the last and the
+/// first item, which moving past either end lands on.
+pub struct Cursor<'a, T> {
+    index: usize,
+    current: usize,
+    deque: &'a ArenaDeque<T>,
+}
+
+/// A position in an [ArenaDeque] that can edit the deque around it.
+///
+/// See [Cursor] for how the cursor moves.
+pub struct CursorMut<'a, T> {
+    index: usize,
+    current: usize,
+    deque: &'a mut ArenaDeque<T>,
+}
+
+impl<T> ArenaDeque<T> {
+    /// A cursor pointing at the first item, or at the ghost position when the deque is empty
+    pub fn cursor_first(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: 0,
+            current: self.first,
+            deque: self,
+        }
+    }
+
+    /// A cursor pointing at the last item, or at the ghost position when the deque is empty
+    pub fn cursor_last(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: self.len.saturating_sub(1),
+            current: self.last,
+            deque: self,
+        }
+    }
+
+    pub fn cursor_first_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            current: self.first,
+            deque: self,
+        }
+    }
+
+    pub fn cursor_last_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: self.len.saturating_sub(1),
+            current: self.last,
+            deque: self,
+        }
+    }
+}
+
+// moves a cursor one position, shared between both kinds of cursor
+macro_rules! move_cursor {
+    ($cursor: expr, right) => {
+        if $cursor.current == NIL {
+            // moving right from the ghost wraps around to the first item
+            $cursor.current = $cursor.deque.first;
+            $cursor.index = 0;
+        } else {
+            $cursor.current = $cursor.deque.slots[$cursor.current].right;
+            $cursor.index += 1;
+        }
+    };
+    ($cursor: expr, left) => {
+        if $cursor.current == NIL {
+            // moving left from the ghost wraps around to the last item
+            $cursor.current = $cursor.deque.last;
+            $cursor.index = $cursor.deque.len.saturating_sub(1);
+        } else {
+            $cursor.current = $cursor.deque.slots[$cursor.current].left;
+            $cursor.index = match $cursor.index.checked_sub(1) {
+                Some(index) => index,
+                // the ghost position is indexed as if it came after the last item
+                None => $cursor.deque.len,
+            };
+        }
+    };
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// The index of the current item, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        if self.current == NIL {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        move_cursor!(self, right);
+    }
+
+    pub fn move_prev(&mut self) {
+        move_cursor!(self, left);
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.deque.value(self.current)
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = if self.current == NIL {
+            self.deque.first
+        } else {
+            self.deque.slots[self.current].right
+        };
+
+        self.deque.value(next)
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = if self.current == NIL {
+            self.deque.last
+        } else {
+            self.deque.slots[self.current].left
+        };
+
+        self.deque.value(prev)
+    }
+}
+
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            deque: self.deque,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// The index of the current item, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        if self.current == NIL {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        move_cursor!(self, right);
+    }
+
+    pub fn move_prev(&mut self) {
+        move_cursor!(self, left);
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.deque.value_mut(self.current)
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = if self.current == NIL {
+            self.deque.first
+        } else {
+            self.deque.slots[self.current].right
+        };
+
+        self.deque.value_mut(next)
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = if self.current == NIL {
+            self.deque.last
+        } else {
+            self.deque.slots[self.current].left
+        };
+
+        self.deque.value_mut(prev)
+    }
+
+    /// A read-only cursor at the same position, borrowing this cursor
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            deque: self.deque,
+        }
+    }
+
+    /// The neighbours between which items are inserted after the current position
+    fn after(&self) -> (usize, usize) {
+        if self.current == NIL {
+            // after the ghost means at the start of the deque
+            (NIL, self.deque.first)
+        } else {
+            (self.current, self.deque.slots[self.current].right)
+        }
+    }
+
+    /// The neighbours between which items are inserted before the current position
+    fn before(&self) -> (usize, usize) {
+        if self.current == NIL {
+            // before the ghost means at the end of the deque
+            (self.deque.last, NIL)
+        } else {
+            (self.deque.slots[self.current].left, self.current)
+        }
+    }
+
+    /// Inserts a value after the current item, or at the start when at the ghost position
+    pub fn insert_after(&mut self, x: T) {
+        let (left, right) = self.after();
+        let index = self.deque.occupy(x);
+        self.deque.link_between(index, left, right);
+
+        if self.current == NIL {
+            self.index += 1;
+        }
+    }
+
+    /// Inserts a value before the current item, or at the end when at the ghost position
+    pub fn insert_before(&mut self, x: T) {
+        let (left, right) = self.before();
+        let index = self.deque.occupy(x);
+        self.deque.link_between(index, left, right);
+
+        self.index += 1;
+    }
+
+    /// Removes the current item and moves the cursor to the item right of it
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.current == NIL {
+            return None;
+        }
+
+        let index = self.current;
+        self.current = self.deque.slots[index].right;
+
+        Some(self.deque.take(index))
+    }
+
+    /// Moves every item right of the cursor into a new deque
+    ///
+    /// The values move into the buffer of the new deque, so this takes O(m) for the m
+    /// values moved, and their handles are rejected afterwards. At the ghost position the
+    /// whole deque is moved, keeping its buffer and handles.
+    pub fn split_after(&mut self) -> ArenaDeque<T> {
+        if self.current == NIL {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        }
+
+        let first = self.deque.slots[self.current].right;
+        let len = self.deque.len - self.index - 1;
+        self.deque.split_range(first, len)
+    }
+
+    /// Moves every item left of the cursor into a new deque
+    ///
+    /// See [CursorMut::split_after] for what this costs.
+    pub fn split_before(&mut self) -> ArenaDeque<T> {
+        if self.current == NIL {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        }
+
+        let len = std::mem::replace(&mut self.index, 0);
+        self.deque.split_range(self.deque.first, len)
+    }
+
+    /// Inserts all values of another deque after the current item
+    ///
+    /// The values move into the buffer of this deque, so this takes O(m) for the m values
+    /// of `other`. At the ghost position the values are inserted at the start.
+    pub fn splice_after(&mut self, other: ArenaDeque<T>) {
+        let len = other.len;
+        let (left, right) = self.after();
+        self.deque.link_values(other, left, right);
+
+        if self.current == NIL {
+            self.index += len;
+        }
+    }
+
+    /// Inserts all values of another deque before the current item
+    ///
+    /// See [CursorMut::splice_after] for what this costs. At the ghost position the values
+    /// are inserted at the end.
+    pub fn splice_before(&mut self, other: ArenaDeque<T>) {
+        let len = other.len;
+        let (left, right) = self.before();
+        self.deque.link_values(other, left, right);
+
+        self.index += len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+
+        #[test]
+        fn a_cursor_on_an_empty_deque_is_at_the_ghost_position() {
+            let data: ArenaDeque<usize> = ArenaDeque::create();
+            let cursor = data.cursor_first();
+
+            assert_eq!(cursor.index(), None);
+            assert_eq!(cursor.current(), None);
+            assert_eq!(cursor.peek_next(), None);
+            assert_eq!(cursor.peek_prev(), None);
+        }
+
+        #[test]
+        fn a_cursor_walks_the_deque_and_wraps_around_the_ghost() {
+            let data: ArenaDeque<usize> = [1, 2, 3].into();
+            let mut cursor = data.cursor_first();
+
+            assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+            cursor.move_next();
+            cursor.move_next();
+            assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+            cursor.move_next();
+            assert_eq!((cursor.index(), cursor.current()), (None, None));
+            assert_eq!(cursor.peek_next(), Some(&1));
+            assert_eq!(cursor.peek_prev(), Some(&3));
+            cursor.move_next();
+            assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+            cursor.move_prev();
+            cursor.move_prev();
+            assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        }
+
+        #[test]
+        fn values_can_be_changed_inserted_and_removed_around_the_cursor() {
+            let mut data: ArenaDeque<usize> = [1, 3, 5].into();
+            let mut cursor = data.cursor_first_mut();
+            cursor.insert_after(2);
+            cursor.insert_before(0);
+            cursor.move_next();
+            *cursor.current().unwrap() *= 10;
+            cursor.move_next();
+
+            assert_eq!(cursor.remove_current(), Some(3));
+            assert_eq!((cursor.index(), cursor.current()), (Some(3), Some(&mut 5)));
+            assert_eq!(cursor.peek_prev(), Some(&mut 20));
+            assert_eq!(values(&data), vec![0, 1, 20, 5]);
+            assert_eq!(values_reversed(&data), vec![0, 1, 20, 5]);
+        }
+
+        #[test]
+        fn inserting_at_the_ghost_position_inserts_at_the_ends() {
+            let mut data: ArenaDeque<usize> = [2].into();
+            let mut cursor = data.cursor_last_mut();
+            cursor.move_next();
+            cursor.insert_after(1);
+            cursor.insert_before(3);
+
+            assert_eq!(cursor.index(), None);
+            cursor.move_prev();
+            assert_eq!(cursor.index(), Some(2));
+            assert_eq!(values(&data), vec![1, 2, 3]);
+            assert_eq!(data.last(), Some(&3));
+        }
+
+        #[test]
+        fn splitting_around_the_cursor() {
+            let mut data: ArenaDeque<usize> = (1..=6).collect();
+            let mut cursor = data.cursor_first_mut();
+            cursor.move_next();
+            cursor.move_next();
+            let tail = cursor.split_after();
+            let head = cursor.split_before();
+
+            assert_eq!(cursor.index(), Some(0));
+            assert_eq!(values(&head), vec![1, 2]);
+            assert_eq!(values(&data), vec![3]);
+            assert_eq!(values_reversed(&tail), vec![4, 5, 6]);
+            assert_eq!((head.len(), data.len(), tail.len()), (2, 1, 3));
+        }
+
+        #[test]
+        fn handles_of_split_values_are_rejected() {
+            let mut data = ArenaDeque::create();
+            let one = data.push_right(1);
+            let two = data.push_right(2);
+            let tail = data.cursor_first_mut().split_after();
+
+            assert_eq!(data.get(one), Some(&1));
+            assert_eq!(data.get(two), None);
+            assert_eq!(values(&tail), vec![2]);
+        }
+
+        #[test]
+        fn splitting_at_the_ghost_position_moves_everything() {
+            let mut data: ArenaDeque<usize> = [1, 2].into();
+            let mut cursor = data.cursor_first_mut();
+            cursor.move_prev();
+            let all = cursor.split_before();
+
+            assert_eq!(values(&all), vec![1, 2]);
+            assert!(data.is_empty());
+            assert!(data.cursor_first_mut().split_after().is_empty());
+        }
+
+        #[test]
+        fn splicing_around_the_cursor() {
+            let mut data: ArenaDeque<usize> = [1, 4].into();
+            let mut cursor = data.cursor_first_mut();
+            cursor.splice_after([2, 3].into());
+            assert_eq!(cursor.index(), Some(0));
+
+            cursor.move_prev();
+            cursor.splice_after([0].into());
+            cursor.splice_before([5, 6].into());
+            cursor.splice_before(ArenaDeque::create());
+
+            assert_eq!(cursor.index(), None);
+            cursor.move_prev();
+            assert_eq!((cursor.index(), cursor.current()), (Some(6), Some(&mut 6)));
+            assert_eq!(values(&data), vec![0, 1, 2, 3, 4, 5, 6]);
+            assert_eq!(values_reversed(&data), vec![0, 1, 2, 3, 4, 5, 6]);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn splitting_around_the_cursor_matches_a_vec(original: Vec<u8>, steps in 0..100usize) {
+                let mut data: ArenaDeque<u8> = original.clone().into();
+                let mut cursor = data.cursor_first_mut();
+                for _ in 0..steps {
+                    cursor.move_next();
+                }
+
+                match cursor.index() {
+                    Some(at) => {
+                        let tail = cursor.split_after();
+                        let head = cursor.split_before();
+
+                        prop_assert_eq!(values(&head), &original[..at]);
+                        prop_assert_eq!(values(&data), &original[at..at + 1]);
+                        prop_assert_eq!(values_reversed(&tail), &original[at + 1..]);
+                        prop_assert_eq!(head.len() + data.len() + tail.len(), original.len());
+                    }
+                    None => {
+                        // at the ghost position everything is moved
+                        let all = cursor.split_after();
+
+                        prop_assert_eq!(values_reversed(&all), original);
+                        prop_assert!(data.is_empty());
+                    }
+                }
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn splicing_around_the_cursor_matches_a_vec(
+                original: Vec<u8>,
+                before: Vec<u8>,
+                after: Vec<u8>,
+                steps in 0..100usize,
+            ) {
+                let mut data: ArenaDeque<u8> = original.clone().into();
+                let mut cursor = data.cursor_first_mut();
+                for _ in 0..steps {
+                    cursor.move_next();
+                }
+                let at = cursor.index();
+                cursor.splice_before(before.clone().into());
+                cursor.splice_after(after.clone().into());
+
+                let expected: Vec<u8> = match at {
+                    Some(at) => {
+                        prop_assert_eq!(cursor.index(), Some(at + before.len()));
+                        [&original[..at], &before, &original[at..at + 1], &after, &original[at + 1..]].concat()
+                    }
+                    None => {
+                        prop_assert_eq!(cursor.index(), None);
+                        [&after[..], &original, &before].concat()
+                    }
+                };
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::deque::arena::{ArenaDeque, Slot};
+use std::marker::PhantomData;
+
+/// Iterates over the values of an [ArenaDeque], from left to right or from right to left
+pub struct Iter<'a, T> {
+    slots: &'a [Slot<T>],
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+/// Iterates over mutable references to the values of an [ArenaDeque]
+pub struct IterMut<'a, T> {
+    // a pointer, as the references handed out must not alias a borrow of the whole buffer
+    slots: *mut Slot<T>,
+    front: usize,
+    back: usize,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+/// Moves the values out of an [ArenaDeque]
+pub struct IntoIter<T> {
+    deque: ArenaDeque<T>,
+}
+
+impl<T> ArenaDeque<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: &self.slots,
+            front: self.first,
+            back: self.last,
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots.as_mut_ptr(),
+            front: self.first,
+            back: self.last,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = &self.slots[self.front];
+        self.front = slot.right;
+        self.len -= 1;
+
+        slot.value.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = &self.slots[self.back];
+        self.back = slot.left;
+        self.len -= 1;
+
+        slot.value.as_ref()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            slots: self.slots,
+            front: self.front,
+            back: self.back,
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // every slot is visited once, so the references never overlap
+        let slot = unsafe { &mut *self.slots.add(self.front) };
+        self.front = slot.right;
+        self.len -= 1;
+
+        slot.value.as_mut()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = unsafe { &mut *self.slots.add(self.back) };
+        self.back = slot.left;
+        self.len -= 1;
+
+        slot.value.as_mut()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_left()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_right()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<'a, T> IntoIterator for &'a ArenaDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArenaDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for ArenaDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque::arena::ArenaDeque;
+
+    #[test]
+    fn iterating_from_both_ends_meets_in_the_middle() {
+        let data: ArenaDeque<usize> = (0..10).collect();
+        let mut iter = data.iter();
+
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.copied().sum::<usize>(), (1..9).sum());
+    }
+
+    #[test]
+    fn values_can_be_changed_while_iterating() {
+        let mut data: ArenaDeque<usize> = (0..5).collect();
+        for value in &mut data {
+            *value *= 2;
+        }
+
+        assert_eq!(data.iter_mut().next_back(), Some(&mut 8));
+        assert_eq!(data.into_iter().collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::deque::arena::{ArenaDeque, NIL};
+
+impl<T> ArenaDeque<T> {
+    /// Reverses the order of the items by swapping their links
+    pub fn reverse(&mut self) {
+        let mut current = self.first;
+        while current != NIL {
+            let slot = &mut self.slots[current];
+            std::mem::swap(&mut slot.left, &mut slot.right);
+            current = slot.left;
+        }
+
+        std::mem::swap(&mut self.first, &mut self.last);
+    }
+
+    /// Rotates the deque `n` places to the left, so that the item at index `n` becomes first
+    ///
+    /// Only the items at the new ends are relinked, after walking O(min(n, len - n)) items.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `n` is larger than the length of the deque.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate by {} in a deque of length {}",
+            n,
+            self.len
+        );
+
+        if n == 0 || n == self.len {
+            return;
+        }
+
+        let new_first = self.slot_at(n).unwrap();
+        let new_last = self.slots[new_first].left;
+
+        // close the deque into a ring, then open it up at the new ends
+        self.slots[self.last].right = self.first;
+        self.slots[self.first].left = self.last;
+        self.slots[new_last].right = NIL;
+        self.slots[new_first].left = NIL;
+
+        self.first = new_first;
+        self.last = new_last;
+    }
+
+    /// Rotates the deque `n` places to the right, so that the first item ends up at index `n`
+    ///
+    /// # Panics
+    ///
+    /// Panics when `n` is larger than the length of the deque.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate by {} in a deque of length {}",
+            n,
+            self.len
+        );
+
+        self.rotate_left(self.len - n);
+    }
+
+    /// Removes consecutive repeated values, keeping the first of each run
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        if self.first == NIL {
+            return;
+        }
+
+        let mut kept = self.first;
+        let mut current = self.slots[kept].right;
+        while current != NIL {
+            let next = self.slots[current].right;
+            if self.slots[current].value == self.slots[kept].value {
+                drop(self.take(current));
+            } else {
+                kept = current;
+            }
+            current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+
+        #[test]
+        fn reversing_swaps_the_ends() {
+            let mut data: ArenaDeque<usize> = (1..=3).collect();
+            data.reverse();
+
+            assert_eq!(values(&data), vec![3, 2, 1]);
+            assert_eq!(values_reversed(&data), vec![3, 2, 1]);
+            assert_eq!(data.first(), Some(&3));
+        }
+
+        #[test]
+        fn rotating_in_both_directions() {
+            let mut data: ArenaDeque<usize> = (1..=5).collect();
+            data.rotate_left(2);
+            assert_eq!(values(&data), vec![3, 4, 5, 1, 2]);
+            assert_eq!(values_reversed(&data), vec![3, 4, 5, 1, 2]);
+
+            data.rotate_right(1);
+            assert_eq!(values(&data), vec![2, 3, 4, 5, 1]);
+        }
+
+        #[test]
+        fn rotating_by_the_length_changes_nothing() {
+            let mut data: ArenaDeque<usize> = [1, 2].into();
+            data.rotate_left(2);
+            data.rotate_right(0);
+
+            assert_eq!(values(&data), vec![1, 2]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rotating_beyond_the_length() {
+            let mut data: ArenaDeque<usize> = [1, 2].into();
+            data.rotate_left(3);
+        }
+
+        #[test]
+        fn deduplicating_removes_consecutive_repeats() {
+            let mut data: ArenaDeque<usize> = [1, 1, 2, 3, 3, 3, 1].into();
+            data.dedup();
+
+            assert_eq!(values(&data), vec![1, 2, 3, 1]);
+            assert_eq!(values_reversed(&data), vec![1, 2, 3, 1]);
+            assert_eq!(data.len(), 4);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn rotating_matches_a_vec(original: Vec<u8>, n: prop::sample::Index) {
+                let n = n.index(original.len() + 1);
+                let mut data: ArenaDeque<u8> = original.clone().into();
+                let mut expected = original;
+                data.rotate_left(n);
+                expected.rotate_left(n);
+
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn deduplicating_matches_a_vec(original in prop::collection::vec(0..4u8, 0..50)) {
+                let mut data: ArenaDeque<u8> = original.clone().into();
+                let mut expected = original;
+                data.dedup();
+                expected.dedup();
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+        }
+    }
+}
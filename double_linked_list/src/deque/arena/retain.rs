@@ -0,0 +1,134 @@
+use crate::deque::arena::{ArenaDeque, NIL};
+
+/// Removes and yields the values matching a predicate, see [ArenaDeque::extract_if]
+pub struct ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    deque: &'a mut ArenaDeque<T>,
+    current: usize,
+    predicate: F,
+}
+
+impl<T> ArenaDeque<T> {
+    /// Keeps only the values matching the predicate, unlinking the others in a single pass
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.extract_if(|value| !predicate(value)).for_each(drop);
+    }
+
+    /// Lazily removes the values matching the predicate, yielding them from left to right
+    ///
+    /// Values that are not reached, because the iterator is dropped early, stay in the deque.
+    pub fn extract_if<F>(&mut self, predicate: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            current: self.first,
+            deque: self,
+            predicate,
+        }
+    }
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current != NIL {
+            let index = self.current;
+            self.current = self.deque.slots[index].right;
+            if (self.predicate)(self.deque.value_mut(index).unwrap()) {
+                return Some(self.deque.take(index));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.deque.len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+
+        #[test]
+        fn retaining_removes_the_other_values() {
+            let mut data: ArenaDeque<usize> = (1..=6).collect();
+            data.retain(|x| x % 2 == 0);
+
+            assert_eq!(values(&data), vec![2, 4, 6]);
+            assert_eq!(values_reversed(&data), vec![2, 4, 6]);
+            assert_eq!(data.len(), 3);
+        }
+
+        #[test]
+        fn extracted_values_are_yielded_in_order_and_can_be_changed() {
+            let mut data: ArenaDeque<usize> = (1..=5).collect();
+            let extracted: Vec<_> = data
+                .extract_if(|x| {
+                    *x *= 10;
+                    *x > 20
+                })
+                .collect();
+
+            assert_eq!(extracted, vec![30, 40, 50]);
+            assert_eq!(values(&data), vec![10, 20]);
+            assert_eq!(data.last(), Some(&20));
+        }
+
+        #[test]
+        fn unreached_values_stay_when_extraction_stops_early() {
+            let mut data: ArenaDeque<usize> = (1..=4).collect();
+            let first = data.extract_if(|x| *x % 2 == 0).next();
+
+            assert_eq!(first, Some(2));
+            assert_eq!(values(&data), vec![1, 3, 4]);
+        }
+
+        #[test]
+        fn handles_of_extracted_values_are_rejected() {
+            let mut data = ArenaDeque::create();
+            let one = data.push_right(1);
+            let two = data.push_right(2);
+            data.retain(|x| *x == 1);
+
+            assert_eq!(data.get(one), Some(&1));
+            assert_eq!(data.get(two), None);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn extracting_matches_a_vec(original: Vec<u8>) {
+                let mut data: ArenaDeque<u8> = original.clone().into();
+                let extracted: Vec<u8> = data.extract_if(|x| *x % 3 == 0).collect();
+                let (expected_extracted, expected): (Vec<u8>, Vec<u8>) =
+                    original.into_iter().partition(|x| x % 3 == 0);
+
+                prop_assert_eq!(extracted, expected_extracted);
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+        }
+    }
+}
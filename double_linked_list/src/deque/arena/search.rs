@@ -0,0 +1,65 @@
+use crate::deque::arena::ArenaDeque;
+
+impl<T> ArenaDeque<T> {
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|value| value == x)
+    }
+
+    /// The index of the first value matching the predicate, searching from the left
+    pub fn position<F>(&self, predicate: F) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    /// The index of the last value matching the predicate, searching from the right
+    pub fn rposition<F>(&self, predicate: F) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter().rposition(predicate)
+    }
+
+    /// The first value matching the predicate, searching from the left
+    pub fn find<F>(&self, mut predicate: F) -> Option<&T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter().find(|value| predicate(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque::arena::ArenaDeque;
+
+    #[test]
+    fn contained_values_are_found() {
+        let data: ArenaDeque<usize> = [1, 2, 3].into();
+
+        assert!(data.contains(&2));
+        assert!(!data.contains(&4));
+    }
+
+    #[test]
+    fn positions_are_searched_from_either_end() {
+        let data: ArenaDeque<usize> = [1, 2, 1, 2].into();
+
+        assert_eq!(data.position(|x| *x == 2), Some(1));
+        assert_eq!(data.rposition(|x| *x == 2), Some(3));
+        assert_eq!(data.position(|x| *x == 3), None);
+        assert_eq!(data.rposition(|x| *x == 3), None);
+    }
+
+    #[test]
+    fn the_first_matching_value_is_found() {
+        let data: ArenaDeque<(u8, char)> = [(1, 'a'), (2, 'b'), (2, 'c')].into();
+
+        assert_eq!(data.find(|x| x.0 == 2), Some(&(2, 'b')));
+        assert_eq!(data.find(|x| x.0 == 3), None);
+    }
+}
@@ -0,0 +1,334 @@
+use crate::deque::arena::{ArenaDeque, NIL};
+use std::cmp::Ordering;
+
+impl<T> ArenaDeque<T> {
+    /// Sorts the deque in place, keeping equal values in their original order
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Sorts the deque in place with a stable, bottom-up merge sort
+    ///
+    /// The slots are relinked rather than moved, so sorting takes O(n log n) comparisons,
+    /// no allocations, and keeps every handle valid. Should the comparison panic, every
+    /// value stays in the deque, in an unspecified order.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len < 2 {
+            return;
+        }
+
+        let list = self.first;
+        let mut runs = Runs::start(self, list);
+
+        // merges runs of `size` items pairwise, doubling `size` until a single run remains
+        let mut size = 1;
+        loop {
+            let mut merges = 0;
+
+            while runs.q != NIL {
+                merges += 1;
+
+                // the second run starts `size` items after the first one
+                runs.p = runs.q;
+                runs.p_size = 0;
+                while runs.p_size < size && runs.q != NIL {
+                    runs.p_size += 1;
+                    runs.q = runs.deque.slots[runs.q].right;
+                }
+                let mut q_size = size;
+
+                while runs.p_size > 0 || (q_size > 0 && runs.q != NIL) {
+                    // taking from the first run on equality keeps the sort stable
+                    let take_p = if runs.p_size == 0 {
+                        false
+                    } else if q_size == 0 || runs.q == NIL {
+                        true
+                    } else {
+                        let (p, q) = runs.values();
+                        compare(p, q) != Ordering::Greater
+                    };
+
+                    if take_p {
+                        runs.take_p();
+                    } else {
+                        runs.take_q();
+                        q_size -= 1;
+                    }
+                }
+            }
+
+            if merges <= 1 {
+                break;
+            }
+            size *= 2;
+            runs.restart();
+        }
+
+        // dropping the runs links the sorted slots back into the deque
+    }
+
+    /// Merges another sorted deque into this sorted deque, leaving `other` empty
+    ///
+    /// The values of `other` move into the buffer of this deque, after which the slots are
+    /// relinked, so merging takes O(n + m) comparisons. Values of this deque come before
+    /// equal values of `other`. Should the comparison panic, the values of both deques end
+    /// up in this deque, in an unspecified order.
+    pub fn merge(&mut self, other: &mut ArenaDeque<T>)
+    where
+        T: Ord,
+    {
+        let a_len = self.len;
+        let a_last = self.last;
+        self.append(other);
+        if a_len == 0 || a_last == self.last {
+            return;
+        }
+
+        // the values of this deque form the first run and those of `other` the second
+        let a = self.first;
+        let b = self.slots[a_last].right;
+        self.slots[a_last].right = NIL;
+
+        let mut runs = Runs::start(self, b);
+        runs.p = a;
+        runs.p_size = a_len;
+        while runs.p_size > 0 && runs.q != NIL {
+            let (p, q) = runs.values();
+            if p <= q {
+                runs.take_p();
+            } else {
+                runs.take_q();
+            }
+        }
+
+        // dropping the runs links whatever remains of either deque after the merged slots
+    }
+}
+
+/// The slots of a deque while they are being merged
+///
+/// Dropping the runs links the merged slots, the rest of the first run and the slots from
+/// the second run onwards back into the deque, so a panicking comparison cannot leave
+/// the links inconsistent.
+struct Runs<'a, T> {
+    deque: &'a mut ArenaDeque<T>,
+    // the merged slots, linked in both directions
+    merged: usize,
+    tail: usize,
+    // the slots of the first run that are left to merge
+    p: usize,
+    p_size: usize,
+    // the rest of the second run, followed by the runs that are still to merge
+    q: usize,
+}
+
+impl<'a, T> Runs<'a, T> {
+    fn start(deque: &'a mut ArenaDeque<T>, list: usize) -> Runs<'a, T> {
+        Runs {
+            deque,
+            merged: NIL,
+            tail: NIL,
+            p: NIL,
+            p_size: 0,
+            q: list,
+        }
+    }
+
+    /// The values at the start of both runs
+    fn values(&self) -> (&T, &T) {
+        (
+            self.deque.value(self.p).unwrap(),
+            self.deque.value(self.q).unwrap(),
+        )
+    }
+
+    /// Starts another pass over the merged slots
+    fn restart(&mut self) {
+        self.deque.slots[self.tail].right = NIL;
+        self.q = std::mem::replace(&mut self.merged, NIL);
+        self.tail = NIL;
+    }
+
+    fn take_p(&mut self) {
+        let next = self.p;
+        self.p = self.deque.slots[next].right;
+        self.p_size -= 1;
+        self.push(next);
+    }
+
+    fn take_q(&mut self) {
+        let next = self.q;
+        self.q = self.deque.slots[next].right;
+        self.push(next);
+    }
+
+    fn push(&mut self, next: usize) {
+        if self.tail == NIL {
+            self.merged = next;
+        } else {
+            self.deque.slots[self.tail].right = next;
+        }
+        self.deque.slots[next].left = self.tail;
+        self.tail = next;
+    }
+}
+
+impl<T> Drop for Runs<'_, T> {
+    fn drop(&mut self) {
+        let slots = &mut self.deque.slots;
+
+        // the rest of the first run continues into the rest of the second one
+        let mut rest = self.q;
+        if self.p_size > 0 {
+            let mut last = self.p;
+            for _ in 1..self.p_size {
+                last = slots[last].right;
+            }
+            slots[last].right = self.q;
+            rest = self.p;
+        }
+
+        if self.tail == NIL {
+            self.merged = rest;
+        } else {
+            slots[self.tail].right = rest;
+        }
+
+        // only the slots that were not merged still need their left links fixed
+        let mut left = self.tail;
+        let mut current = rest;
+        while current != NIL {
+            slots[current].left = left;
+            left = current;
+            current = slots[current].right;
+        }
+
+        self.deque.first = self.merged;
+        self.deque.last = left;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+
+        #[test]
+        fn sorting_relinks_both_directions_and_keeps_handles() {
+            let mut data = ArenaDeque::create();
+            for x in [5, 3, 1, 4] {
+                data.push_right(x);
+            }
+            let two = data.push_right(2);
+            data.sort();
+
+            assert_eq!(values(&data), vec![1, 2, 3, 4, 5]);
+            assert_eq!(values_reversed(&data), vec![1, 2, 3, 4, 5]);
+            assert_eq!(data.remove(two), Some(2));
+        }
+
+        #[test]
+        fn sorting_is_stable() {
+            let mut data: ArenaDeque<(u8, char)> = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')].into();
+            data.sort_by_key(|x| x.0);
+
+            assert_eq!(values(&data), vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+        }
+
+        #[test]
+        fn a_panicking_comparison_keeps_every_value() {
+            let mut data: ArenaDeque<usize> = (0..20).rev().collect();
+            let mut comparisons = 0;
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                data.sort_by(|a, b| {
+                    comparisons += 1;
+                    if comparisons == 30 {
+                        panic!("cannot compare");
+                    }
+                    a.cmp(b)
+                });
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(data.len(), 20);
+            let mut sorted = values(&data);
+            assert_eq!(values_reversed(&data), sorted);
+            sorted.sort();
+            assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn merging_sorted_deques() {
+            let mut a: ArenaDeque<usize> = [1, 3, 5, 7].into();
+            let mut b: ArenaDeque<usize> = [2, 3, 4].into();
+            a.merge(&mut b);
+
+            assert_eq!(values(&a), vec![1, 2, 3, 3, 4, 5, 7]);
+            assert_eq!(values_reversed(&a), vec![1, 2, 3, 3, 4, 5, 7]);
+            assert!(b.is_empty());
+        }
+
+        #[test]
+        fn merging_with_empty_deques() {
+            let mut a: ArenaDeque<usize> = [1, 2].into();
+            let mut b = ArenaDeque::create();
+            a.merge(&mut b);
+            b.merge(&mut a);
+
+            assert!(a.is_empty());
+            assert_eq!(values(&b), vec![1, 2]);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn sorting_matches_a_stable_sort(original: Vec<(u8, usize)>) {
+                let mut data: ArenaDeque<(u8, usize)> = original.clone().into();
+                let mut expected = original;
+                expected.sort_by_key(|x| x.0);
+                data.sort_by_key(|x| x.0);
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn merging_matches_sorting_the_concatenation(mut a: Vec<u8>, mut b: Vec<u8>) {
+                a.sort();
+                b.sort();
+                let mut data: ArenaDeque<u8> = a.clone().into();
+                data.merge(&mut b.clone().into());
+                let mut expected = [a, b].concat();
+                expected.sort();
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::deque::arena::{ArenaDeque, NIL};
+
+impl<T> ArenaDeque<T> {
+    /// Moves all values of `other` to the right end of this deque, leaving `other` empty
+    ///
+    /// The values move into the buffer of this deque, so this takes O(m) for the m values
+    /// of `other`. The handles of `other` are invalidated, while those of this deque stay
+    /// valid, and `other` keeps its buffer for later pushes.
+    pub fn append(&mut self, other: &mut ArenaDeque<T>) {
+        let values = std::iter::from_fn(|| other.pop_left());
+        self.link_values(values, self.last, NIL);
+    }
+
+    /// Moves all values of `other` to the left end of this deque, leaving `other` empty
+    ///
+    /// See [ArenaDeque::append] for what this costs.
+    pub fn prepend(&mut self, other: &mut ArenaDeque<T>) {
+        let values = std::iter::from_fn(|| other.pop_left());
+        self.link_values(values, NIL, self.first);
+    }
+
+    /// Splits the deque in two at an index, returning the values from that index onwards
+    ///
+    /// The values move into the buffer of the new deque, so this takes O(len - at) after
+    /// finding the index, and their handles are rejected afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `at` is larger than the length of the deque.
+    pub fn split_off(&mut self, at: usize) -> ArenaDeque<T> {
+        assert!(
+            at <= self.len,
+            "Cannot split off at {} in a deque of length {}",
+            at,
+            self.len
+        );
+
+        match self.slot_at(at) {
+            Some(first) => self.split_range(first, self.len - at),
+            None => ArenaDeque::create(),
+        }
+    }
+
+    /// Inserts all values of `other` at an index, so that its first value ends up at that index
+    ///
+    /// The values move into the buffer of this deque, so this takes O(m) for the m values
+    /// of `other` after finding the index.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `at` is larger than the length of the deque.
+    pub fn splice(&mut self, at: usize, other: ArenaDeque<T>) {
+        assert!(
+            at <= self.len,
+            "Cannot splice at {} in a deque of length {}",
+            at,
+            self.len
+        );
+
+        match self.slot_at(at) {
+            Some(right) => self.link_values(other, self.slots[right].left, right),
+            None => self.link_values(other, self.last, NIL),
+        }
+    }
+
+    /// Moves `len` values, starting at the slot `first`, into a new deque
+    pub(in crate::deque::arena) fn split_range(
+        &mut self,
+        first: usize,
+        len: usize,
+    ) -> ArenaDeque<T> {
+        let mut split = ArenaDeque::with_capacity(len);
+        let mut current = first;
+        for _ in 0..len {
+            let next = self.slots[current].right;
+            split.push_right(self.take(current));
+            current = next;
+        }
+
+        split
+    }
+
+    /// Links the values, in order, between two neighbouring slots
+    pub(in crate::deque::arena) fn link_values<I>(
+        &mut self,
+        values: I,
+        mut left: usize,
+        right: usize,
+    ) where
+        I: IntoIterator<Item = T>,
+    {
+        for x in values {
+            let index = self.occupy(x);
+            self.link_between(index, left, right);
+            left = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+
+        #[test]
+        fn appending_and_prepending_move_every_value() {
+            let mut data: ArenaDeque<usize> = [2, 3].into();
+            let mut left: ArenaDeque<usize> = [0, 1].into();
+            let mut right: ArenaDeque<usize> = [4, 5].into();
+            data.append(&mut right);
+            data.prepend(&mut left);
+
+            assert_eq!(values(&data), vec![0, 1, 2, 3, 4, 5]);
+            assert_eq!(values_reversed(&data), vec![0, 1, 2, 3, 4, 5]);
+            assert_eq!(data.len(), 6);
+            assert!(left.is_empty() && right.is_empty());
+        }
+
+        #[test]
+        fn handles_of_the_receiving_deque_stay_valid() {
+            let mut a = ArenaDeque::create();
+            let mut b = ArenaDeque::create();
+            let one = a.push_right(1);
+            let two = b.push_right(2);
+            a.append(&mut b);
+
+            assert_eq!(a.get(one), Some(&1));
+            assert_eq!(b.get(two), None);
+
+            // the emptied deque reuses its slot without accepting the old handle
+            let three = b.push_right(3);
+            assert_eq!(b.get(two), None);
+            assert_eq!(b.get(three), Some(&3));
+        }
+
+        #[test]
+        fn splitting_off_in_the_middle() {
+            let mut a: ArenaDeque<usize> = (1..=5).collect();
+            let b = a.split_off(2);
+
+            assert_eq!(values(&a), vec![1, 2]);
+            assert_eq!(values_reversed(&b), vec![3, 4, 5]);
+            assert_eq!(a.last(), Some(&2));
+        }
+
+        #[test]
+        fn splitting_off_at_the_ends() {
+            let mut a: ArenaDeque<usize> = [1, 2].into();
+            let handle = a.push_right(3);
+
+            assert!(a.split_off(3).is_empty());
+            assert_eq!(values(&a.split_off(0)), vec![1, 2, 3]);
+            assert!(a.is_empty());
+            assert_eq!(a.get(handle), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn splitting_off_beyond_the_end() {
+            let mut a: ArenaDeque<usize> = [1, 2].into();
+            a.split_off(3);
+        }
+
+        #[test]
+        fn splicing_at_an_index() {
+            let mut a: ArenaDeque<usize> = [1, 4].into();
+            a.splice(1, [2, 3].into());
+            a.splice(0, [0].into());
+            a.splice(5, [5].into());
+
+            assert_eq!(values(&a), vec![0, 1, 2, 3, 4, 5]);
+            assert_eq!(values_reversed(&a), vec![0, 1, 2, 3, 4, 5]);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::arena::ArenaDeque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn splitting_off_and_splicing_back_restores_the_deque(original: Vec<u8>, at: prop::sample::Index) {
+                let at = at.index(original.len() + 1);
+                let mut data: ArenaDeque<u8> = original.clone().into();
+                let split = data.split_off(at);
+
+                prop_assert_eq!(values(&data), &original[..at]);
+                prop_assert_eq!(values_reversed(&split), &original[at..]);
+
+                data.splice(at, split);
+                prop_assert_eq!(data.len(), original.len());
+                prop_assert_eq!(values(&data), original.clone());
+                prop_assert_eq!(values_reversed(&data), original);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn appending_and_prepending_match_a_vec(a: Vec<u8>, b: Vec<u8>, c: Vec<u8>) {
+                let mut data: ArenaDeque<u8> = b.clone().into();
+                data.prepend(&mut a.clone().into());
+                data.append(&mut c.clone().into());
+                let expected = [a, b, c].concat();
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use crate::deque::Deque;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// A deque shared between threads, where pops wait for values and pushes wait for room.
+///
+/// After [BlockingDeque::close], pushes are refused while the values still held can be
+/// popped; pops only report the deque as closed once it is also empty.
+pub struct BlockingDeque<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: Option<usize>,
+}
+
+struct State<T> {
+    deque: Deque<T>,
+    closed: bool,
+}
+
+/// A push onto a closed [BlockingDeque], handing the value back
+#[derive(Debug, PartialEq, Eq)]
+pub struct PushError<T>(pub T);
+
+/// Why [BlockingDeque::try_pop_left] or [BlockingDeque::try_pop_right] returned no value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryPopError {
+    Empty,
+    Closed,
+}
+
+/// Why [BlockingDeque::pop_left_timeout] or [BlockingDeque::pop_right_timeout] returned no value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopTimeoutError {
+    Timeout,
+    Closed,
+}
+
+impl<T> BlockingDeque<T> {
+    /// Creates a deque without a capacity, so pushes never wait
+    pub fn create() -> BlockingDeque<T> {
+        Self::with_capacity_option(None)
+    }
+
+    /// Creates a deque holding at most `capacity` values, so pushes wait while it is full
+    ///
+    /// # Panics
+    ///
+    /// Panics when `capacity` is 0.
+    pub fn bounded(capacity: usize) -> BlockingDeque<T> {
+        assert!(
+            capacity > 0,
+            "A bounded deque needs room for at least one value"
+        );
+
+        Self::with_capacity_option(Some(capacity))
+    }
+
+    fn with_capacity_option(capacity: Option<usize>) -> BlockingDeque<T> {
+        BlockingDeque {
+            state: Mutex::new(State {
+                deque: Deque::create(),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().deque.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().deque.is_empty()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Refuses further pushes and wakes every waiting thread
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// Pushes a value to the left, waiting while the deque is full
+    pub fn push_left(&self, x: T) -> Result<(), PushError<T>> {
+        self.push_with(x, |deque, x| {
+            deque.push_left(x);
+        })
+    }
+
+    /// Pushes a value to the right, waiting while the deque is full
+    pub fn push_right(&self, x: T) -> Result<(), PushError<T>> {
+        self.push_with(x, |deque, x| {
+            deque.push_right(x);
+        })
+    }
+
+    /// Pops the leftmost value, waiting while the deque is empty
+    ///
+    /// Returns `None` once the deque is closed and empty.
+    pub fn pop_left(&self) -> Option<T> {
+        self.pop_with(Deque::pop_left)
+    }
+
+    /// Pops the rightmost value, waiting while the deque is empty
+    ///
+    /// Returns `None` once the deque is closed and empty.
+    pub fn pop_right(&self) -> Option<T> {
+        self.pop_with(Deque::pop_right)
+    }
+
+    pub fn pop_left_timeout(&self, timeout: Duration) -> Result<T, PopTimeoutError> {
+        self.pop_timeout_with(timeout, Deque::pop_left)
+    }
+
+    pub fn pop_right_timeout(&self, timeout: Duration) -> Result<T, PopTimeoutError> {
+        self.pop_timeout_with(timeout, Deque::pop_right)
+    }
+
+    pub fn try_pop_left(&self) -> Result<T, TryPopError> {
+        self.try_pop_with(Deque::pop_left)
+    }
+
+    pub fn try_pop_right(&self) -> Result<T, TryPopError> {
+        self.try_pop_with(Deque::pop_right)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        // every operation leaves the deque consistent, so a panicking thread cannot corrupt it
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_full(&self, state: &State<T>) -> bool {
+        self.capacity
+            .is_some_and(|capacity| state.deque.len() >= capacity)
+    }
+
+    fn push_with(&self, x: T, push: impl FnOnce(&mut Deque<T>, T)) -> Result<(), PushError<T>> {
+        let mut state = self
+            .not_full
+            .wait_while(self.lock(), |state| !state.closed && self.is_full(state))
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if state.closed {
+            return Err(PushError(x));
+        }
+        push(&mut state.deque, x);
+        drop(state);
+
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    fn pop_with(&self, pop: fn(&mut Deque<T>) -> Option<T>) -> Option<T> {
+        let mut state = self
+            .not_empty
+            .wait_while(self.lock(), |state| !state.closed && state.deque.is_empty())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let popped = pop(&mut state.deque);
+        drop(state);
+
+        if popped.is_some() {
+            self.not_full.notify_one();
+        }
+        popped
+    }
+
+    fn pop_timeout_with(
+        &self,
+        timeout: Duration,
+        pop: fn(&mut Deque<T>) -> Option<T>,
+    ) -> Result<T, PopTimeoutError> {
+        let (mut state, _) = self
+            .not_empty
+            .wait_timeout_while(self.lock(), timeout, |state| {
+                !state.closed && state.deque.is_empty()
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let popped = pop(&mut state.deque);
+        let closed = state.closed;
+        drop(state);
+
+        match popped {
+            Some(x) => {
+                self.not_full.notify_one();
+                Ok(x)
+            }
+            None if closed => Err(PopTimeoutError::Closed),
+            None => Err(PopTimeoutError::Timeout),
+        }
+    }
+
+    fn try_pop_with(&self, pop: fn(&mut Deque<T>) -> Option<T>) -> Result<T, TryPopError> {
+        let mut state = self.lock();
+        let popped = pop(&mut state.deque);
+        let closed = state.closed;
+        drop(state);
+
+        match popped {
+            Some(x) => {
+                self.not_full.notify_one();
+                Ok(x)
+            }
+            None if closed => Err(TryPopError::Closed),
+            None => Err(TryPopError::Empty),
+        }
+    }
+}
+
+impl<T> Default for BlockingDeque<T> {
+    fn default() -> Self {
+        BlockingDeque::create()
+    }
+}
+
+impl<T: Debug> Debug for BlockingDeque<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("BlockingDeque")
+            .field("deque", &state.deque)
+            .field("closed", &state.closed)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+impl<T> Display for PushError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot push onto a closed deque")
+    }
+}
+
+impl<T: Debug> Error for PushError<T> {}
+
+impl Display for TryPopError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryPopError::Empty => write!(f, "the deque is empty"),
+            TryPopError::Closed => write!(f, "the deque is closed and empty"),
+        }
+    }
+}
+
+impl Error for TryPopError {}
+
+impl Display for PopTimeoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PopTimeoutError::Timeout => write!(f, "timed out waiting for a value"),
+            PopTimeoutError::Closed => write!(f, "the deque is closed and empty"),
+        }
+    }
+}
+
+impl Error for PopTimeoutError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque::blocking::{BlockingDeque, PopTimeoutError, PushError, TryPopError};
+    use std::time::Duration;
+
+    #[test]
+    fn values_are_popped_from_either_end() {
+        let data = BlockingDeque::create();
+        data.push_right(2).unwrap();
+        data.push_left(1).unwrap();
+        data.push_right(3).unwrap();
+
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.pop_left(), Some(1));
+        assert_eq!(data.pop_right(), Some(3));
+        assert_eq!(data.try_pop_right(), Ok(2));
+        assert_eq!(data.try_pop_left(), Err(TryPopError::Empty));
+    }
+
+    #[test]
+    fn popping_an_empty_deque_times_out() {
+        let data: BlockingDeque<usize> = BlockingDeque::create();
+
+        assert_eq!(
+            data.pop_left_timeout(Duration::from_millis(10)),
+            Err(PopTimeoutError::Timeout)
+        );
+        data.push_left(1).unwrap();
+        assert_eq!(data.pop_right_timeout(Duration::from_millis(10)), Ok(1));
+    }
+
+    #[test]
+    fn closing_refuses_pushes_but_keeps_the_values() {
+        let data = BlockingDeque::create();
+        data.push_right(String::from("kept")).unwrap();
+        data.close();
+
+        assert!(data.is_closed());
+        assert_eq!(
+            data.push_right(String::from("refused")),
+            Err(PushError(String::from("refused")))
+        );
+        assert_eq!(data.pop_left().as_deref(), Some("kept"));
+        assert_eq!(data.pop_left(), None);
+        assert_eq!(data.try_pop_right(), Err(TryPopError::Closed));
+        assert_eq!(
+            data.pop_right_timeout(Duration::from_secs(1)),
+            Err(PopTimeoutError::Closed)
+        );
+    }
+
+    #[test]
+    fn debug_output_shows_the_values() {
+        let data = BlockingDeque::bounded(2);
+        data.push_right(1).unwrap();
+
+        assert_eq!(
+            format!("{:?}", data),
+            "BlockingDeque { deque: [1], closed: false, capacity: Some(2) }"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_capacity_of_zero_is_refused() {
+        let _: BlockingDeque<usize> = BlockingDeque::bounded(0);
+    }
+}
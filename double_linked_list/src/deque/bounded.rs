@@ -0,0 +1,301 @@
+use crate::deque::handle::NodeHandle;
+use crate::deque::position::Position;
+use crate::deque::Deque;
+
+/// What a [BoundedDeque] does with a push when it is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Rejects the push, handing the value back
+    Reject,
+    /// Evicts the value at the opposite end, e.g. the leftmost value when pushing right
+    EvictOpposite,
+    /// Evicts the value at the end being pushed to, e.g. the rightmost value when pushing right
+    EvictSame,
+}
+
+/// The outcome of a push onto a [BoundedDeque]
+#[must_use]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Pushed<T> {
+    /// The value was pushed without evicting anything
+    Added(NodeHandle),
+    /// The value was pushed after evicting another value to make room
+    Evicted(NodeHandle, T),
+    /// The deque was full and the value was rejected
+    Rejected(T),
+}
+
+impl<T> Pushed<T> {
+    /// The handle of the pushed value, or `None` when it was rejected
+    pub fn handle(&self) -> Option<NodeHandle> {
+        match self {
+            Pushed::Added(handle) | Pushed::Evicted(handle, _) => Some(*handle),
+            Pushed::Rejected(_) => None,
+        }
+    }
+}
+
+/// A deque holding at most `max_len` values, applying an [OverflowPolicy] to pushes when full
+#[derive(Debug)]
+pub struct BoundedDeque<T> {
+    deque: Deque<T>,
+    max_len: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T> BoundedDeque<T> {
+    /// # Panics
+    ///
+    /// Panics when `max_len` is 0.
+    pub fn with_max_len(max_len: usize, policy: OverflowPolicy) -> BoundedDeque<T> {
+        assert!(
+            max_len > 0,
+            "A bounded deque needs room for at least one value"
+        );
+
+        BoundedDeque {
+            deque: Deque::create(),
+            max_len,
+            policy,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.deque.len() == self.max_len
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn push_left(&mut self, x: T) -> Pushed<T> {
+        if !self.is_full() {
+            return Pushed::Added(self.deque.push_left(x));
+        }
+
+        let evicted = match self.policy {
+            OverflowPolicy::Reject => return Pushed::Rejected(x),
+            OverflowPolicy::EvictOpposite => self.deque.pop_right(),
+            OverflowPolicy::EvictSame => self.deque.pop_left(),
+        };
+        let handle = self.deque.push_left(x);
+
+        // a full deque is never empty, as `max_len` is at least 1
+        Pushed::Evicted(handle, evicted.unwrap())
+    }
+
+    pub fn push_right(&mut self, x: T) -> Pushed<T> {
+        if !self.is_full() {
+            return Pushed::Added(self.deque.push_right(x));
+        }
+
+        let evicted = match self.policy {
+            OverflowPolicy::Reject => return Pushed::Rejected(x),
+            OverflowPolicy::EvictOpposite => self.deque.pop_left(),
+            OverflowPolicy::EvictSame => self.deque.pop_right(),
+        };
+        let handle = self.deque.push_right(x);
+
+        Pushed::Evicted(handle, evicted.unwrap())
+    }
+
+    pub fn pop_left(&mut self) -> Option<T> {
+        self.deque.pop_left()
+    }
+
+    pub fn pop_right(&mut self) -> Option<T> {
+        self.deque.pop_right()
+    }
+
+    /// The value at an index or behind a handle returned by a push
+    pub fn get<P: Position>(&self, position: P) -> Option<&T> {
+        self.deque.get(position)
+    }
+
+    pub fn get_mut<P: Position>(&mut self, position: P) -> Option<&mut T> {
+        self.deque.get_mut(position)
+    }
+
+    /// Removes a value by index or by the handle returned when pushing it
+    ///
+    /// Handles of evicted values are rejected, so a handle never removes another value.
+    pub fn remove<P: Position>(&mut self, position: P) -> Option<T> {
+        self.deque.remove(position)
+    }
+
+    /// Read access to the underlying deque, which cannot be changed past the bound
+    pub fn as_deque(&self) -> &Deque<T> {
+        &self.deque
+    }
+
+    pub fn into_deque(self) -> Deque<T> {
+        self.deque
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::bounded::{BoundedDeque, OverflowPolicy, Pushed};
+
+        #[test]
+        fn pushes_below_the_bound_are_added() {
+            let mut data = BoundedDeque::with_max_len(2, OverflowPolicy::Reject);
+
+            assert!(matches!(data.push_right(1), Pushed::Added(_)));
+            assert!(matches!(data.push_left(0), Pushed::Added(_)));
+            assert!(data.is_full());
+            assert_eq!(data.as_deque(), &deque![0, 1]);
+        }
+
+        #[test]
+        fn rejecting_hands_the_value_back() {
+            let mut data = BoundedDeque::with_max_len(1, OverflowPolicy::Reject);
+            let _ = data.push_right(String::from("kept"));
+
+            assert_eq!(
+                data.push_right(String::from("rejected")),
+                Pushed::Rejected(String::from("rejected"))
+            );
+            assert_eq!(data.as_deque(), &deque![String::from("kept")]);
+        }
+
+        #[test]
+        fn evicting_the_opposite_end_keeps_the_most_recent_values() {
+            let mut data = BoundedDeque::with_max_len(3, OverflowPolicy::EvictOpposite);
+            let mut evicted = vec![];
+            for x in 1..=5 {
+                if let Pushed::Evicted(_, value) = data.push_right(x) {
+                    evicted.push(value);
+                }
+            }
+
+            assert_eq!(evicted, vec![1, 2]);
+            assert_eq!(data.as_deque(), &deque![3, 4, 5]);
+
+            let pushed = data.push_left(2);
+            assert!(matches!(pushed, Pushed::Evicted(_, 5)));
+            assert_eq!(data.as_deque().get(pushed.handle().unwrap()), Some(&2));
+        }
+
+        #[test]
+        fn evicting_the_same_end_replaces_the_value_there() {
+            let mut data = BoundedDeque::with_max_len(2, OverflowPolicy::EvictSame);
+            let _ = data.push_right(1);
+            let _ = data.push_right(2);
+
+            assert!(matches!(data.push_right(3), Pushed::Evicted(_, 2)));
+            assert!(matches!(data.push_left(0), Pushed::Evicted(_, 1)));
+            assert_eq!(data.into_deque(), deque![0, 3]);
+        }
+
+        #[test]
+        fn handles_of_pushed_values_change_and_remove_them() {
+            let mut data = BoundedDeque::with_max_len(2, OverflowPolicy::EvictOpposite);
+            let one = data.push_right(1).handle().unwrap();
+            let two = data.push_right(2).handle().unwrap();
+            *data.get_mut(two).unwrap() += 10;
+
+            assert_eq!(data.remove(two), Some(12));
+            assert_eq!(data.get(two), None);
+            assert!(!data.is_full());
+            assert_eq!(data.get(0), Some(&1));
+
+            let _ = data.push_right(3);
+            let _ = data.push_right(4);
+            assert_eq!(data.remove(one), None);
+            assert_eq!(data.as_deque(), &deque![3, 4]);
+        }
+
+        #[test]
+        fn popping_makes_room_again() {
+            let mut data = BoundedDeque::with_max_len(1, OverflowPolicy::Reject);
+            let _ = data.push_left(1);
+
+            assert_eq!(data.pop_right(), Some(1));
+            assert!(data.is_empty());
+            assert!(matches!(data.push_left(2), Pushed::Added(_)));
+        }
+
+        #[test]
+        fn rejected_pushes_have_no_handle() {
+            let mut data = BoundedDeque::with_max_len(1, OverflowPolicy::Reject);
+            let _ = data.push_left(1);
+
+            assert_eq!(data.push_left(2).handle(), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn a_bound_of_zero_is_refused() {
+            let _: BoundedDeque<usize> = BoundedDeque::with_max_len(0, OverflowPolicy::Reject);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::bounded::{BoundedDeque, OverflowPolicy, Pushed};
+        use proptest::prelude::*;
+        use std::collections::VecDeque;
+
+        fn policy() -> impl Strategy<Value = OverflowPolicy> {
+            prop_oneof![
+                Just(OverflowPolicy::Reject),
+                Just(OverflowPolicy::EvictOpposite),
+                Just(OverflowPolicy::EvictSame),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn pushes_match_a_bounded_vec_deque(
+                max_len in 1..8usize,
+                policy in policy(),
+                pushes in prop::collection::vec((any::<bool>(), any::<u8>()), 0..50)
+            ) {
+                let mut data = BoundedDeque::with_max_len(max_len, policy);
+                let mut expected = VecDeque::new();
+
+                for (left, x) in pushes {
+                    let full = expected.len() == max_len;
+                    let expected_evicted = match (full, policy, left) {
+                        (false, _, true) => { expected.push_front(x); None }
+                        (false, _, false) => { expected.push_back(x); None }
+                        (true, OverflowPolicy::Reject, _) => None,
+                        (true, OverflowPolicy::EvictOpposite, true) => { let e = expected.pop_back(); expected.push_front(x); e }
+                        (true, OverflowPolicy::EvictOpposite, false) => { let e = expected.pop_front(); expected.push_back(x); e }
+                        (true, OverflowPolicy::EvictSame, true) => { let e = expected.pop_front(); expected.push_front(x); e }
+                        (true, OverflowPolicy::EvictSame, false) => { let e = expected.pop_back(); expected.push_back(x); e }
+                    };
+
+                    let pushed = if left { data.push_left(x) } else { data.push_right(x) };
+                    match pushed {
+                        Pushed::Added(_) => prop_assert!(!full),
+                        Pushed::Evicted(_, value) => prop_assert_eq!(Some(value), expected_evicted),
+                        Pushed::Rejected(value) => {
+                            prop_assert_eq!(policy, OverflowPolicy::Reject);
+                            prop_assert_eq!(value, x);
+                        }
+                    }
+
+                    prop_assert!(data.len() <= max_len);
+                    prop_assert_eq!(data.as_deque().iter().copied().collect::<Vec<_>>(), expected.iter().copied().collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,6 @@
 use crate::deque::Deque;
 
-impl<T: Clone> Clone for Deque<T>
-where
-    T: PartialEq,
-{
+impl<T: Clone> Clone for Deque<T> {
     fn clone(&self) -> Self {
         let mut cloned = Deque::create();
 
@@ -0,0 +1,84 @@
+use crate::deque::Deque;
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Deque::create()
+    }
+}
+
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push_right(x);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::create();
+        deque.extend(iter);
+
+        deque
+    }
+}
+
+impl<T> From<Vec<T>> for Deque<T> {
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Deque<T> {
+    fn from(values: [T; N]) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque;
+    use crate::deque::Deque;
+
+    #[test]
+    fn the_default_deque_is_empty() {
+        let data: Deque<usize> = Deque::default();
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn a_deque_can_be_collected() {
+        let data: Deque<usize> = (1..=3).collect();
+
+        assert_eq!(data, deque![1, 2, 3]);
+        assert_eq!(data.last().unwrap().value(), &3);
+    }
+
+    #[test]
+    fn a_deque_can_be_extended() {
+        let mut data = deque![1];
+        data.extend(vec![2, 3]);
+        data.extend(&[4, 5]);
+
+        assert_eq!(data, deque![1, 2, 3, 4, 5]);
+        assert_eq!(data.len(), 5);
+    }
+
+    #[test]
+    fn a_deque_can_be_made_from_a_vec() {
+        let data = Deque::from(vec![String::from("a"), String::from("b")]);
+        assert_eq!(data, deque![String::from("a"), String::from("b")]);
+    }
+
+    #[test]
+    fn a_deque_can_be_made_from_an_array() {
+        let data: Deque<usize> = [1, 2, 3].into();
+        assert_eq!(data, deque![1, 2, 3]);
+    }
+}
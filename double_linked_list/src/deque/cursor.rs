@@ -0,0 +1,527 @@
+use crate::deque::item::Item;
+use crate::deque::Deque;
+
+/// A read-only position in a deque that can move in both directions.
+///
+/// Besides pointing at an item, a cursor can point at a "ghost" position that sits
+/// between the last and the first item. Moving right from the last item or left from
+/// the first item lands on the ghost, and moving on from the ghost wraps around.
+pub struct Cursor<'a, T> {
+    index: usize,
+    current: *mut Item<T>,
+    deque: &'a Deque<T>,
+}
+
+/// A position in a deque that can edit the deque around it in O(1).
+///
+/// See [Cursor] for how the cursor moves.
+pub struct CursorMut<'a, T> {
+    index: usize,
+    current: *mut Item<T>,
+    deque: &'a mut Deque<T>,
+}
+
+impl<T> Deque<T> {
+    /// A cursor pointing at the first item, or at the ghost position when the deque is empty
+    pub fn cursor_first(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: 0,
+            current: self.first,
+            deque: self,
+        }
+    }
+
+    /// A cursor pointing at the last item, or at the ghost position when the deque is empty
+    pub fn cursor_last(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: self.len.saturating_sub(1),
+            current: self.last,
+            deque: self,
+        }
+    }
+
+    pub fn cursor_first_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            current: self.first,
+            deque: self,
+        }
+    }
+
+    pub fn cursor_last_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: self.len.saturating_sub(1),
+            current: self.last,
+            deque: self,
+        }
+    }
+}
+
+// moves a cursor one position, shared between both kinds of cursor
+macro_rules! move_cursor {
+    ($cursor: expr, right) => {
+        if $cursor.current.is_null() {
+            // moving right from the ghost wraps around to the first item
+            $cursor.current = $cursor.deque.first;
+            $cursor.index = 0;
+        } else {
+            $cursor.current = unsafe { (*$cursor.current).right_ptr };
+            $cursor.index += 1;
+        }
+    };
+    ($cursor: expr, left) => {
+        if $cursor.current.is_null() {
+            // moving left from the ghost wraps around to the last item
+            $cursor.current = $cursor.deque.last;
+            $cursor.index = $cursor.deque.len.saturating_sub(1);
+        } else {
+            $cursor.current = unsafe { (*$cursor.current).left_ptr };
+            $cursor.index = match $cursor.index.checked_sub(1) {
+                Some(index) => index,
+                // the ghost position is indexed as if it came after the last item
+                None => $cursor.deque.len,
+            };
+        }
+    };
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// The index of the current item, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        if self.current.is_null() {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        move_cursor!(self, right);
+    }
+
+    pub fn move_prev(&mut self) {
+        move_cursor!(self, left);
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.current.as_ref() }.map(|item| &item.value)
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = if self.current.is_null() {
+            self.deque.first
+        } else {
+            unsafe { (*self.current).right_ptr }
+        };
+
+        unsafe { next.as_ref() }.map(|item| &item.value)
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = if self.current.is_null() {
+            self.deque.last
+        } else {
+            unsafe { (*self.current).left_ptr }
+        };
+
+        unsafe { prev.as_ref() }.map(|item| &item.value)
+    }
+}
+
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            deque: self.deque,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// The index of the current item, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        if self.current.is_null() {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        move_cursor!(self, right);
+    }
+
+    pub fn move_prev(&mut self) {
+        move_cursor!(self, left);
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.current.as_mut() }.map(|item| &mut item.value)
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = if self.current.is_null() {
+            self.deque.first
+        } else {
+            unsafe { (*self.current).right_ptr }
+        };
+
+        unsafe { next.as_mut() }.map(|item| &mut item.value)
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = if self.current.is_null() {
+            self.deque.last
+        } else {
+            unsafe { (*self.current).left_ptr }
+        };
+
+        unsafe { prev.as_mut() }.map(|item| &mut item.value)
+    }
+
+    /// A read-only cursor at the same position, borrowing this cursor
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            deque: self.deque,
+        }
+    }
+
+    /// The neighbours between which items are inserted after the current position
+    fn after(&self) -> (*mut Item<T>, *mut Item<T>) {
+        if self.current.is_null() {
+            // after the ghost means at the start of the deque
+            (std::ptr::null_mut(), self.deque.first)
+        } else {
+            (self.current, unsafe { (*self.current).right_ptr })
+        }
+    }
+
+    /// The neighbours between which items are inserted before the current position
+    fn before(&self) -> (*mut Item<T>, *mut Item<T>) {
+        if self.current.is_null() {
+            // before the ghost means at the end of the deque
+            (self.deque.last, std::ptr::null_mut())
+        } else {
+            (unsafe { (*self.current).left_ptr }, self.current)
+        }
+    }
+
+    /// Inserts a value after the current item, or at the start when at the ghost position
+    pub fn insert_after(&mut self, x: T) {
+        let (left_ptr, right_ptr) = self.after();
+        let ptr = self.deque.allocate(x);
+        unsafe { self.deque.link_between(ptr, left_ptr, right_ptr) };
+
+        if self.current.is_null() {
+            self.index += 1;
+        }
+    }
+
+    /// Inserts a value before the current item, or at the end when at the ghost position
+    pub fn insert_before(&mut self, x: T) {
+        let (left_ptr, right_ptr) = self.before();
+        let ptr = self.deque.allocate(x);
+        unsafe { self.deque.link_between(ptr, left_ptr, right_ptr) };
+
+        self.index += 1;
+    }
+
+    /// Removes the current item and moves the cursor to the item right of it
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.current.is_null() {
+            return None;
+        }
+
+        let ptr = self.current;
+        unsafe {
+            self.current = (*ptr).right_ptr;
+            self.deque.unlink(ptr);
+
+            Some(self.deque.release(ptr))
+        }
+    }
+
+    /// Moves every item right of the cursor into a new deque
+    ///
+    /// At the ghost position the whole deque is moved.
+    pub fn split_after(&mut self) -> Deque<T> {
+        if self.current.is_null() {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        }
+
+        let first_ptr = unsafe { (*self.current).right_ptr };
+        if first_ptr.is_null() {
+            Deque::create()
+        } else {
+            let len = self.deque.len - self.index - 1;
+            unsafe { self.deque.detach(first_ptr, self.deque.last, len) }
+        }
+    }
+
+    /// Moves every item left of the cursor into a new deque
+    ///
+    /// At the ghost position the whole deque is moved.
+    pub fn split_before(&mut self) -> Deque<T> {
+        if self.current.is_null() {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        }
+
+        let last_ptr = unsafe { (*self.current).left_ptr };
+        if last_ptr.is_null() {
+            Deque::create()
+        } else {
+            let len = self.index;
+            self.index = 0;
+            unsafe { self.deque.detach(self.deque.first, last_ptr, len) }
+        }
+    }
+
+    /// Inserts all items of another deque after the current item in O(1)
+    ///
+    /// At the ghost position the items are inserted at the start.
+    pub fn splice_after(&mut self, other: Deque<T>) {
+        let len = other.len;
+        let (left_ptr, right_ptr) = self.after();
+        unsafe { self.deque.splice_between(other, left_ptr, right_ptr) };
+
+        if self.current.is_null() {
+            self.index += len;
+        }
+    }
+
+    /// Inserts all items of another deque before the current item in O(1)
+    ///
+    /// At the ghost position the items are inserted at the end.
+    pub fn splice_before(&mut self, other: Deque<T>) {
+        let len = other.len;
+        let (left_ptr, right_ptr) = self.before();
+        unsafe { self.deque.splice_between(other, left_ptr, right_ptr) };
+
+        self.index += len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque;
+    use crate::deque::Deque;
+    use crate::test_helpers::values;
+
+    #[test]
+    fn a_cursor_on_an_empty_deque_is_at_the_ghost_position() {
+        let data: Deque<usize> = deque![];
+        let cursor = data.cursor_first();
+
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), None);
+    }
+
+    #[test]
+    fn a_cursor_walks_the_deque_and_wraps_around_the_ghost() {
+        let data = deque![1, 2, 3];
+        let mut cursor = data.cursor_first();
+
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&2)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&3));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+    }
+
+    #[test]
+    fn a_cursor_can_start_at_the_last_item() {
+        let data = deque![1, 2, 3];
+        let mut cursor = data.cursor_last();
+
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        cursor.move_prev();
+        assert_eq!(cursor.peek_prev(), Some(&1));
+        assert_eq!(cursor.peek_next(), Some(&3));
+    }
+
+    #[test]
+    fn the_current_value_can_be_changed() {
+        let mut data = deque![1, 2, 3];
+        let mut cursor = data.cursor_first_mut();
+        cursor.move_next();
+        *cursor.current().unwrap() = 20;
+
+        assert_eq!(values(&data), vec![1, 20, 3]);
+    }
+
+    #[test]
+    fn values_can_be_inserted_around_the_cursor() {
+        let mut data = deque![1, 3];
+        let mut cursor = data.cursor_first_mut();
+        cursor.insert_after(2);
+        cursor.insert_before(0);
+
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(values(&data), vec![0, 1, 2, 3]);
+        assert_eq!(data.len(), 4);
+    }
+
+    #[test]
+    fn inserting_at_the_ghost_position_inserts_at_the_ends() {
+        let mut data = deque![2];
+        let mut cursor = data.cursor_first_mut();
+        cursor.move_next();
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(values(&data), vec![1, 2, 3]);
+        assert_eq!(data.last().unwrap().value(), &3);
+    }
+
+    #[test]
+    fn inserting_into_an_empty_deque() {
+        let mut data: Deque<usize> = deque![];
+        data.cursor_first_mut().insert_before(2);
+        data.cursor_first_mut().insert_before(1);
+
+        assert_eq!(values(&data), vec![1, 2]);
+    }
+
+    #[test]
+    fn removing_moves_the_cursor_to_the_next_item() {
+        let mut data = deque![1, 2, 3];
+        let mut cursor = data.cursor_first_mut();
+        cursor.move_next();
+
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 3)));
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(values(&data), vec![1]);
+        assert_eq!(data.last().unwrap().value(), &1);
+    }
+
+    #[test]
+    fn removing_every_item_empties_the_deque() {
+        let mut data = deque![String::from("a"), String::from("b")];
+        let mut cursor = data.cursor_first_mut();
+        cursor.remove_current();
+        cursor.remove_current();
+
+        assert!(data.is_empty());
+        assert_eq!(data.first(), None);
+        assert_eq!(data.last(), None);
+    }
+
+    #[test]
+    fn splitting_after_the_cursor() {
+        let mut data = deque![1, 2, 3, 4];
+        let mut cursor = data.cursor_first_mut();
+        cursor.move_next();
+        let tail = cursor.split_after();
+
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(values(&data), vec![1, 2]);
+        assert_eq!(values(&tail), vec![3, 4]);
+        assert_eq!((data.len(), tail.len()), (2, 2));
+        assert_eq!(data.last().unwrap().right(), None);
+        assert_eq!(tail.first().unwrap().left(), None);
+    }
+
+    #[test]
+    fn splitting_before_the_cursor() {
+        let mut data = deque![1, 2, 3, 4];
+        let mut cursor = data.cursor_last_mut();
+        cursor.move_prev();
+        let head = cursor.split_before();
+
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(values(&head), vec![1, 2]);
+        assert_eq!(values(&data), vec![3, 4]);
+        assert_eq!((head.len(), data.len()), (2, 2));
+    }
+
+    #[test]
+    fn splitting_at_the_ends_returns_an_empty_deque() {
+        let mut data = deque![1, 2];
+        assert!(data.cursor_last_mut().split_after().is_empty());
+        assert!(data.cursor_first_mut().split_before().is_empty());
+        assert_eq!(values(&data), vec![1, 2]);
+    }
+
+    #[test]
+    fn splitting_at_the_ghost_position_moves_everything() {
+        let mut data = deque![1, 2];
+        let mut cursor = data.cursor_first_mut();
+        cursor.move_prev();
+        let all = cursor.split_after();
+
+        assert_eq!(values(&all), vec![1, 2]);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn splicing_after_the_cursor() {
+        let mut data = deque![1, 4];
+        let mut cursor = data.cursor_first_mut();
+        cursor.splice_after(deque![2, 3]);
+
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(values(&data), vec![1, 2, 3, 4]);
+        assert_eq!(data.len(), 4);
+    }
+
+    #[test]
+    fn splicing_before_the_cursor() {
+        let mut data = deque![1, 4];
+        let mut cursor = data.cursor_last_mut();
+        cursor.splice_before(deque![2, 3]);
+
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        assert_eq!(values(&data), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn splicing_at_the_ghost_position_adds_to_the_ends() {
+        let mut data = deque![2];
+        let mut cursor = data.cursor_first_mut();
+        cursor.move_next();
+        cursor.splice_after(deque![0, 1]);
+        cursor.splice_before(deque![3, 4]);
+        cursor.splice_before(deque![]);
+
+        assert_eq!(cursor.index(), None);
+        assert_eq!(values(&data), vec![0, 1, 2, 3, 4]);
+        assert_eq!(data.first().unwrap().value(), &0);
+        assert_eq!(data.last().unwrap().value(), &4);
+    }
+
+    #[test]
+    fn splicing_into_an_empty_deque() {
+        let mut data: Deque<usize> = deque![];
+        data.cursor_first_mut().splice_before(deque![1, 2]);
+
+        assert_eq!(values(&data), vec![1, 2]);
+        assert_eq!(data.len(), 2);
+    }
+}
@@ -1,9 +1,9 @@
-use crate::deque::{Deque, DequeTypeRequirements};
+use crate::deque::Deque;
 
-impl<T: DequeTypeRequirements> PartialEq for Deque<T> {
+impl<T: PartialEq> PartialEq for Deque<T> {
     fn eq(&self, other: &Self) -> bool {
-        let mut iter_self = self.into_iter();
-        let mut iter_other = other.into_iter();
+        let mut iter_self = self.iter();
+        let mut iter_other = other.iter();
 
         loop {
             return match (iter_self.next(), iter_other.next()) {
@@ -15,7 +15,7 @@ impl<T: DequeTypeRequirements> PartialEq for Deque<T> {
                 // self is shorter than other, so not equal
                 (None, Some(_)) => false,
                 // not equal if values don't match
-                (Some(x1), Some(x2)) if x1.value() != x2.value() => false,
+                (Some(x1), Some(x2)) if x1 != x2 => false,
                 // no reason to think they're different, so keep looking
                 _ => continue,
             };
@@ -23,6 +23,8 @@ impl<T: DequeTypeRequirements> PartialEq for Deque<T> {
     }
 }
 
+impl<T: Eq> Eq for Deque<T> {}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,32 @@
+use crate::deque::Deque;
+use std::fmt::{Debug, Formatter};
+
+impl<T: Debug> Debug for Deque<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque;
+    use crate::deque::Deque;
+
+    #[test]
+    fn debug_output_lists_the_values() {
+        let data = deque![1, 2, 3];
+        assert_eq!(format!("{:?}", data), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn debug_output_of_an_empty_deque() {
+        let data: Deque<usize> = deque![];
+        assert_eq!(format!("{:?}", data), "[]");
+    }
+
+    #[test]
+    fn debug_output_respects_the_alternate_flag() {
+        let data = deque!["a"];
+        assert_eq!(format!("{:#?}", data), "[\n    \"a\",\n]");
+    }
+}
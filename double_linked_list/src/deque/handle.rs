@@ -0,0 +1,356 @@
+use crate::deque::item::Item;
+use crate::deque::position::{sealed, Position};
+use crate::deque::Deque;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Ids are unique across all deques and never reused, so a handle can never be mistaken
+/// for an item of another deque.
+static NEXT_DEQUE_ID: AtomicU64 = AtomicU64::new(1);
+
+pub(in crate::deque) fn next_id() -> u64 {
+    NEXT_DEQUE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A copyable reference to an item, returned when pushing a value.
+///
+/// Unlike a borrowed `&Item<T>`, a handle does not keep the deque borrowed, so it can be
+/// stored and used later for O(1) access or removal. A handle is only accepted by the
+/// deque that handed it out, and only for as long as its item is part of it: handles of
+/// removed items, of items moved into another deque and of a deque that items were split
+/// off from are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    deque: u64,
+    item: *const (),
+    generation: u64,
+}
+
+// a handle is an opaque token, its pointer is only followed by the deque that handed it out
+unsafe impl Send for NodeHandle {}
+unsafe impl Sync for NodeHandle {}
+
+impl sealed::Locate for NodeHandle {
+    fn locate<T>(&self, deque: &Deque<T>) -> *mut Item<T> {
+        deque.locate(*self).unwrap_or(std::ptr::null_mut())
+    }
+}
+
+impl Position for NodeHandle {}
+
+impl<T> Deque<T> {
+    /// Creates an item for a value, reusing a released item when there is one
+    pub(in crate::deque) fn allocate(&mut self, x: T) -> *mut Item<T> {
+        if self.free.is_null() {
+            return Item::create(x);
+        }
+
+        let ptr = self.free;
+        unsafe {
+            self.free = (*ptr).right_ptr;
+
+            // the previous value was moved out on release, so it must not be dropped here
+            std::ptr::addr_of_mut!((*ptr).value).write(x);
+            (*ptr).left_ptr = std::ptr::null_mut();
+            (*ptr).right_ptr = std::ptr::null_mut();
+        }
+
+        ptr
+    }
+
+    /// Hands out a handle for an item of this deque
+    pub(in crate::deque) fn register(&self, ptr: *mut Item<T>) -> NodeHandle {
+        NodeHandle {
+            deque: self.id,
+            item: ptr as *const (),
+            generation: unsafe { (*ptr).generation },
+        }
+    }
+
+    /// Moves the value out of an unlinked item and keeps the item for reuse, invalidating
+    /// its handle
+    ///
+    /// # Safety
+    ///
+    /// The item must have been part of this deque and must not be used afterwards.
+    pub(in crate::deque) unsafe fn release(&mut self, ptr: *mut Item<T>) -> T {
+        let value = std::ptr::addr_of!((*ptr).value).read();
+        (*ptr).generation += 1;
+        (*ptr).left_ptr = std::ptr::null_mut();
+        (*ptr).right_ptr = self.free;
+        self.free = ptr;
+
+        value
+    }
+
+    fn locate(&self, handle: NodeHandle) -> Option<*mut Item<T>> {
+        // items handed out under this id are never deallocated while the id is in use, as
+        // splitting items off renews it, so the pointer can be followed safely
+        if handle.deque != self.id {
+            return None;
+        }
+
+        let ptr = handle.item as *mut Item<T>;
+        if unsafe { (*ptr).generation } == handle.generation {
+            Some(ptr)
+        } else {
+            None
+        }
+    }
+
+    /// Moves an item to the left end of the deque, returning whether the handle was valid
+    pub fn move_to_front(&mut self, handle: NodeHandle) -> bool {
+        match self.locate(handle) {
+            None => false,
+            Some(ptr) => {
+                if ptr != self.first {
+                    unsafe {
+                        self.unlink(ptr);
+                        self.link_between(ptr, std::ptr::null_mut(), self.first);
+                    }
+                }
+
+                true
+            }
+        }
+    }
+
+    /// Moves an item to the right end of the deque, returning whether the handle was valid
+    pub fn move_to_back(&mut self, handle: NodeHandle) -> bool {
+        match self.locate(handle) {
+            None => false,
+            Some(ptr) => {
+                if ptr != self.last {
+                    unsafe {
+                        self.unlink(ptr);
+                        self.link_between(ptr, self.last, std::ptr::null_mut());
+                    }
+                }
+
+                true
+            }
+        }
+    }
+
+    /// Inserts a value right of an item, or gives the value back when the handle is invalid
+    pub fn insert_after(&mut self, handle: NodeHandle, x: T) -> Result<NodeHandle, T> {
+        match self.locate(handle) {
+            None => Err(x),
+            Some(ptr) => {
+                let created = self.allocate(x);
+                unsafe { self.link_between(created, ptr, (*ptr).right_ptr) };
+
+                Ok(self.register(created))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque;
+    use crate::deque::Deque;
+    use crate::test_helpers::values;
+
+    #[test]
+    fn a_pushed_value_can_be_read_through_its_handle() {
+        let mut data = Deque::create();
+        let left = data.push_left(1);
+        let right = data.push_right(2);
+
+        assert_eq!(data.get(left), Some(&1));
+        assert_eq!(data.get(right), Some(&2));
+    }
+
+    #[test]
+    fn a_value_can_be_changed_through_its_handle() {
+        let mut data = Deque::create();
+        let handle = data.push_right(1);
+        *data.get_mut(handle).unwrap() = 10;
+
+        assert_eq!(values(&data), vec![10]);
+    }
+
+    #[test]
+    fn handles_can_be_copied_and_outlive_further_pushes() {
+        let mut data = Deque::create();
+        let handle = data.push_right(String::from("kept"));
+        let copy = handle;
+        for n in 0..10 {
+            data.push_left(n.to_string());
+        }
+
+        assert_eq!(data.get(copy), Some(&String::from("kept")));
+    }
+
+    #[test]
+    fn an_item_in_the_middle_can_be_removed() {
+        let mut data = Deque::create();
+        data.push_right(1);
+        let middle = data.push_right(2);
+        data.push_right(3);
+
+        assert_eq!(data.remove(middle), Some(2));
+        assert_eq!(values(&data), vec![1, 3]);
+        assert_eq!(data.len(), 2);
+    }
+
+    #[test]
+    fn the_ends_can_be_removed() {
+        let mut data = Deque::create();
+        let first = data.push_right(1);
+        data.push_right(2);
+        let last = data.push_right(3);
+
+        assert_eq!(data.remove(first), Some(1));
+        assert_eq!(data.remove(last), Some(3));
+        assert_eq!(data.first().unwrap().value(), &2);
+        assert_eq!(data.last().unwrap().value(), &2);
+    }
+
+    #[test]
+    fn handles_of_removed_items_are_rejected() {
+        let mut data = Deque::create();
+        let handle = data.push_right(1);
+        data.remove(handle);
+
+        assert_eq!(data.get(handle), None);
+        assert_eq!(data.remove(handle), None);
+        assert!(!data.move_to_front(handle));
+    }
+
+    #[test]
+    fn handles_of_popped_items_are_rejected() {
+        let mut data = Deque::create();
+        let left = data.push_right(1);
+        let right = data.push_right(2);
+        data.pop_left();
+        data.pop_right();
+
+        assert_eq!(data.get(left), None);
+        assert_eq!(data.get(right), None);
+    }
+
+    #[test]
+    fn handles_of_other_deques_are_rejected() {
+        let mut a = Deque::create();
+        let mut b = Deque::create();
+        let handle = a.push_right(1);
+        b.push_right(1);
+
+        assert_eq!(b.get(handle), None);
+        assert_eq!(b.remove(handle), None);
+        assert_eq!(b.insert_after(handle, 2), Err(2));
+        assert_eq!(b.len(), 1);
+    }
+
+    #[test]
+    fn handles_of_clones_are_not_shared() {
+        let mut data = Deque::create();
+        let handle = data.push_right(1);
+        let cloned = data.clone();
+
+        assert_eq!(cloned.get(handle), None);
+        assert_eq!(data.get(handle), Some(&1));
+    }
+
+    #[test]
+    fn items_can_be_moved_to_the_ends() {
+        let mut data = Deque::create();
+        let one = data.push_right(1);
+        let two = data.push_right(2);
+        data.push_right(3);
+
+        assert!(data.move_to_back(one));
+        assert_eq!(values(&data), vec![2, 3, 1]);
+        assert!(data.move_to_front(one));
+        assert_eq!(values(&data), vec![1, 2, 3]);
+        assert!(data.move_to_front(two));
+        assert_eq!(values(&data), vec![2, 1, 3]);
+        assert!(data.move_to_front(two));
+        assert_eq!(values(&data), vec![2, 1, 3]);
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.last().unwrap().value(), &3);
+    }
+
+    #[test]
+    fn values_can_be_inserted_after_an_item() {
+        let mut data = Deque::create();
+        let one = data.push_right(1);
+        let three = data.push_right(3);
+
+        let two = data.insert_after(one, 2).unwrap();
+        let four = data.insert_after(three, 4).unwrap();
+
+        assert_eq!(values(&data), vec![1, 2, 3, 4]);
+        assert_eq!(data.get(two), Some(&2));
+        assert_eq!(data.last().unwrap().value(), &4);
+        assert_eq!(data.remove(four), Some(4));
+    }
+
+    #[test]
+    fn handles_are_rejected_after_splitting_off() {
+        let mut data = Deque::create();
+        let one = data.push_right(1);
+        let two = data.push_right(2);
+        data.push_right(3);
+
+        let mut cursor = data.cursor_first_mut();
+        let tail = cursor.split_after();
+
+        assert_eq!(data.get(one), None);
+        assert_eq!(data.get(two), None);
+        assert_eq!(tail.get(two), None);
+        assert_eq!(values(&tail), vec![2, 3]);
+    }
+
+    #[test]
+    fn handles_of_spliced_items_are_rejected() {
+        let mut data = deque![1, 4];
+        let mut other = Deque::create();
+        let two = other.push_right(2);
+        other.push_right(3);
+
+        data.cursor_first_mut().splice_after(other);
+
+        assert_eq!(data.remove(two), None);
+        assert_eq!(values(&data), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn released_items_are_reused_without_accepting_old_handles() {
+        let mut data = Deque::create();
+        let old = data.push_right(String::from("old"));
+        data.pop_right();
+        let new = data.push_right(String::from("new"));
+
+        assert_eq!(data.get(old), None);
+        assert_eq!(data.get(new), Some(&String::from("new")));
+        assert_eq!(data.len(), 1);
+    }
+
+    #[test]
+    fn handles_survive_a_panicking_sort() {
+        let mut data = Deque::create();
+        let handle = data.push_right(2);
+        data.push_right(1);
+
+        let sorted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            data.sort_by(|_, _| panic!("cannot compare"))
+        }));
+
+        assert!(sorted.is_err());
+        assert_eq!(data.get(handle), Some(&2));
+        assert_eq!(data.len(), 2);
+    }
+
+    #[test]
+    fn items_inserted_with_a_cursor_release_cleanly() {
+        let mut data = Deque::create();
+        let handle = data.push_right(String::from("a"));
+        data.cursor_first_mut().insert_after(String::from("b"));
+        data.cursor_last_mut().remove_current();
+
+        assert_eq!(data.get(handle), Some(&String::from("a")));
+        assert_eq!(data.len(), 1);
+    }
+}
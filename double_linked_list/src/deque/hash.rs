@@ -0,0 +1,60 @@
+use crate::deque::Deque;
+use std::hash::{Hash, Hasher};
+
+impl<T: Hash> Hash for Deque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // the length prefix keeps nested deques like [[1], [2]] and [[1, 2]] apart
+        state.write_usize(self.len);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::Deque;
+        use std::collections::HashSet;
+
+        #[test]
+        fn equal_deques_are_interchangeable_as_keys() {
+            let mut set = HashSet::new();
+            set.insert(deque![1, 2, 3]);
+
+            assert!(set.contains(&deque![1, 2, 3]));
+            assert!(!set.contains(&deque![3, 2, 1]));
+        }
+
+        #[test]
+        fn nested_deques_are_told_apart() {
+            let mut set: HashSet<Deque<Deque<usize>>> = HashSet::new();
+            set.insert(deque![deque![1], deque![2]]);
+
+            assert!(!set.contains(&deque![deque![1, 2]]));
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::Deque;
+        use proptest::prelude::*;
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn cloned_deques_hash_the_same(original: Deque<usize>) {
+                prop_assert_eq!(hash_of(&original), hash_of(&original.clone()));
+            }
+        }
+    }
+}
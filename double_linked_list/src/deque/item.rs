@@ -1,24 +1,25 @@
 use std::ptr;
 
-#[derive(Debug, Clone)]
-pub struct Item<T>
-where
-    T: PartialEq,
-{
+/// An item of a [Deque](crate::deque::Deque), borrowed from it to read its value or to walk
+/// to its neighbours.
+///
+/// Items cannot be cloned, as a copy would keep pointing at neighbours the deque may free.
+#[derive(Debug)]
+pub struct Item<T> {
     pub(in crate::deque) left_ptr: *mut Item<T>,
     pub(in crate::deque) right_ptr: *mut Item<T>,
     pub(in crate::deque) value: T,
+    // increased whenever the item is released, so handles to earlier values are rejected
+    pub(in crate::deque) generation: u64,
 }
 
-impl<T> Item<T>
-where
-    T: PartialEq,
-{
+impl<T> Item<T> {
     pub(in crate::deque) fn create(value: T) -> *mut Item<T> {
         let item = Item {
             left_ptr: std::ptr::null_mut(),
             right_ptr: std::ptr::null_mut(),
             value,
+            generation: 0,
         };
 
         let layout = std::alloc::Layout::new::<Item<T>>();
@@ -34,6 +35,27 @@ where
         ptr
     }
 
+    /// Deallocates an item created by [Item::create], moving its value out
+    ///
+    /// # Safety
+    ///
+    /// The pointer must come from [Item::create] and must not be used afterwards.
+    pub(in crate::deque) unsafe fn destroy(ptr: *mut Item<T>) -> T {
+        let item = ptr::read(ptr);
+        std::alloc::dealloc(ptr as *mut u8, std::alloc::Layout::new::<Item<T>>());
+
+        item.value
+    }
+
+    /// Deallocates an item whose value has already been moved out
+    ///
+    /// # Safety
+    ///
+    /// The pointer must come from [Item::create] and must not be used afterwards.
+    pub(in crate::deque) unsafe fn deallocate(ptr: *mut Item<T>) {
+        std::alloc::dealloc(ptr as *mut u8, std::alloc::Layout::new::<Item<T>>());
+    }
+
     pub fn value(&self) -> &T {
         &self.value
     }
@@ -53,7 +75,9 @@ where
             Some(unsafe { &*self.right_ptr })
         }
     }
+}
 
+impl<T: PartialEq> Item<T> {
     fn equal_values(&self, other: &Self) -> bool {
         self.value == other.value
     }
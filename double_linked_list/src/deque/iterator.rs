@@ -1,39 +1,300 @@
 use crate::deque::item::Item;
-use crate::deque::{Deque, DequeTypeRequirements};
+use crate::deque::Deque;
+use std::marker::PhantomData;
 
-pub struct DequeIterator<'a, T: DequeTypeRequirements> {
-    current: Option<&'a Item<T>>,
+/// Iterates over the values of a deque by reference, see [Deque::iter]
+pub struct Iter<'a, T> {
+    left: *mut Item<T>,
+    right: *mut Item<T>,
+    // the number of items between left and right (inclusive) that were not yielded yet
+    len: usize,
+    marker: PhantomData<&'a Item<T>>,
 }
 
-impl<'a, T: DequeTypeRequirements> Iterator for DequeIterator<'a, T> {
-    type Item = &'a Item<T>;
+/// Iterates over the values of a deque by mutable reference, see [Deque::iter_mut]
+pub struct IterMut<'a, T> {
+    left: *mut Item<T>,
+    right: *mut Item<T>,
+    len: usize,
+    marker: PhantomData<&'a mut Item<T>>,
+}
+
+/// Iterates over the items of a deque, giving access to their neighbours, see [Deque::nodes]
+pub struct Nodes<'a, T> {
+    left: *mut Item<T>,
+    right: *mut Item<T>,
+    len: usize,
+    marker: PhantomData<&'a Item<T>>,
+}
+
+/// Moves the values out of a deque, see [Deque::into_iter]
+pub struct IntoIter<T> {
+    deque: Deque<T>,
+}
+
+impl<T> Deque<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            left: self.first,
+            right: self.last,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            left: self.first,
+            right: self.last,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn nodes(&self) -> Nodes<'_, T> {
+        Nodes {
+            left: self.first,
+            right: self.last,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+// walks the items from both ends, shared between the borrowing iterators
+// note that the ends cannot cross, since iteration stops once `len` items were yielded
+macro_rules! walk {
+    ($iterator: expr, left) => {
+        if $iterator.len == 0 {
+            None
+        } else {
+            let ptr = $iterator.left;
+            $iterator.left = unsafe { (*ptr).right_ptr };
+            $iterator.len -= 1;
+            Some(ptr)
+        }
+    };
+    ($iterator: expr, right) => {
+        if $iterator.len == 0 {
+            None
+        } else {
+            let ptr = $iterator.right;
+            $iterator.right = unsafe { (*ptr).left_ptr };
+            $iterator.len -= 1;
+            Some(ptr)
+        }
+    };
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let current_item = self.current;
-        self.current = match self.current {
-            // if there is no current item, no right item can be found
-            None => None,
-            // if there is a current item, pick the next right, which may be a None
-            // note that there are two levels of Option here
-            // lvl1 -> the optionality of a next iterator item
-            // lvl2 -> the optionality of a right link on the item
-            Some(item) => match item.right() {
-                None => None,
-                Some(item) => Some(item),
-            },
-        };
+        walk!(self, left).map(|ptr| unsafe { &(*ptr).value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
 
-        current_item
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        walk!(self, right).map(|ptr| unsafe { &(*ptr).value })
     }
 }
 
-impl<'a, T: DequeTypeRequirements> IntoIterator for &'a Deque<T> {
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        walk!(self, left).map(|ptr| unsafe { &mut (*ptr).value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        walk!(self, right).map(|ptr| unsafe { &mut (*ptr).value })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for Nodes<'a, T> {
     type Item = &'a Item<T>;
-    type IntoIter = DequeIterator<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        walk!(self, left).map(|ptr| unsafe { &*ptr })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Nodes<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        walk!(self, right).map(|ptr| unsafe { &*ptr })
+    }
+}
+
+impl<T> ExactSizeIterator for Nodes<'_, T> {}
+
+impl<T> Clone for Nodes<'_, T> {
+    fn clone(&self) -> Self {
+        Nodes { ..*self }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_left()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len(), Some(self.deque.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_right()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        DequeIterator {
-            current: self.first(),
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque;
+    use crate::deque::Deque;
+
+    #[test]
+    fn iterating_an_empty_deque() {
+        let data: Deque<usize> = deque![];
+
+        assert_eq!(data.iter().next(), None);
+        assert_eq!(data.iter().next_back(), None);
+        assert_eq!(data.iter().len(), 0);
+    }
+
+    #[test]
+    fn iterating_yields_values_from_left_to_right() {
+        let data = deque![1, 2, 3];
+
+        assert_eq!(data.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(data.iter().rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    }
+
+    #[test]
+    fn iterating_from_both_ends_meets_in_the_middle() {
+        let data = deque![1, 2, 3, 4];
+        let mut iter = data.iter();
+
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn values_can_be_changed_while_iterating() {
+        let mut data = deque![1, 2, 3];
+        for x in data.iter_mut() {
+            *x *= 10;
+        }
+        for x in &mut data {
+            *x += 1;
         }
+
+        assert_eq!(data, deque![11, 21, 31]);
+    }
+
+    #[test]
+    fn values_can_be_changed_from_both_ends() {
+        let mut data = deque![1, 2, 3];
+        let mut iter = data.iter_mut();
+        *iter.next_back().unwrap() = 30;
+        *iter.next().unwrap() = 10;
+        assert_eq!(iter.len(), 1);
+
+        assert_eq!(data, deque![10, 2, 30]);
+    }
+
+    #[test]
+    fn owned_values_can_be_moved_out() {
+        let data = deque![String::from("a"), String::from("b"), String::from("c")];
+        let mut iter = data.into_iter();
+
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(String::from("c")));
+        assert_eq!(
+            iter.collect::<Vec<_>>(),
+            vec![String::from("a"), String::from("b")]
+        );
+    }
+
+    #[test]
+    fn partially_consumed_owning_iterators_drop_the_remaining_values() {
+        let data = deque![Box::new(1), Box::new(2), Box::new(3)];
+        let mut iter = data.into_iter();
+
+        assert_eq!(iter.next(), Some(Box::new(1)));
+    }
+
+    #[test]
+    fn nodes_give_access_to_neighbours() {
+        let data = deque![1, 2, 3];
+        let middle = data.nodes().nth(1).unwrap();
+
+        assert_eq!(middle.value(), &2);
+        assert_eq!(middle.left().unwrap().value(), &1);
+        assert_eq!(middle.right().unwrap().value(), &3);
+        assert_eq!(data.nodes().next_back().unwrap().right(), None);
     }
 }
@@ -1,14 +1,10 @@
+/// Creates a [Deque](crate::deque::Deque) holding the given values, from left to right
+///
+/// The deque is built through its `FromIterator` implementation, like `collect` would.
 #[macro_export]
 macro_rules! deque {
-    ( $( $x:expr ),* ) => {
-        {
-            #[allow(unused_mut)]
-            let mut temp_deque = crate::deque::Deque::create();
-            $(
-                temp_deque.push_right($x);
-            )*
-            temp_deque
-        }
+    ( $( $x:expr ),* $(,)? ) => {
+        <$crate::deque::Deque<_> as ::core::iter::FromIterator<_>>::from_iter([$( $x ),*])
     };
 }
 
@@ -32,6 +28,12 @@ mod tests {
         assert_eq!(data.len(), 1);
     }
 
+    #[test]
+    fn create_deque_with_trailing_comma() {
+        let data = deque![1, 2,];
+        assert_eq!(data.len(), 2);
+    }
+
     #[test]
     fn create_deque_2() {
         let data = deque![1, 2, 4];
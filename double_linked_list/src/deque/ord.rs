@@ -0,0 +1,66 @@
+use crate::deque::Deque;
+use std::cmp::Ordering;
+
+impl<T: PartialOrd> PartialOrd for Deque<T> {
+    /// Compares the values lexicographically, from left to right
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for Deque<T> {
+    /// Compares the values lexicographically, from left to right
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::Deque;
+        use std::cmp::Ordering;
+
+        #[test]
+        fn the_first_differing_value_decides() {
+            assert!(deque![1, 2, 3] < deque![1, 3]);
+            assert!(deque![2] > deque![1, 9, 9]);
+        }
+
+        #[test]
+        fn a_prefix_comes_first() {
+            let empty: Deque<usize> = deque![];
+
+            assert!(deque![1, 2] < deque![1, 2, 0]);
+            assert!(empty < deque![0]);
+        }
+
+        #[test]
+        fn equal_deques_compare_equal() {
+            assert_eq!(deque![1, 2].cmp(&deque![1, 2]), Ordering::Equal);
+        }
+
+        #[test]
+        fn incomparable_values_make_deques_incomparable() {
+            assert_eq!(deque![1.0, f64::NAN].partial_cmp(&deque![1.0, 2.0]), None);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::Deque;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn ordering_matches_a_vec(a: Deque<u8>, b: Deque<u8>) {
+                let vec_a: Vec<u8> = a.iter().cloned().collect();
+                let vec_b: Vec<u8> = b.iter().cloned().collect();
+
+                prop_assert_eq!(a.cmp(&b), vec_a.cmp(&vec_b));
+            }
+        }
+    }
+}
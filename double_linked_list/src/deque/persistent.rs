@@ -0,0 +1,618 @@
+use std::cell::{Cell, OnceCell};
+use std::fmt;
+use std::rc::Rc;
+
+/// How much longer one side of a [PersistentDeque] may grow than the other before rebalancing
+const BALANCE: usize = 3;
+
+struct Node<T> {
+    value: T,
+    next: Stream<T>,
+}
+
+/// A lazy list, which is either empty or a shared cell computing its first node on demand.
+///
+/// The first node is computed at most once and then shared by every version holding the
+/// cell, which is what keeps the amortised bounds when old versions are reused.
+struct Stream<T>(Option<Rc<Lazy<T>>>);
+
+struct Lazy<T> {
+    // taken when the cell is forced
+    pending: Cell<Option<Suspension<T>>>,
+    forced: OnceCell<Option<Node<T>>>,
+}
+
+/// Work on other streams that has not been carried out yet
+struct Suspension<T> {
+    operation: Operation<T>,
+    // copies the values into the new nodes, captured where `T: Clone` is known
+    clone: fn(&T) -> T,
+}
+
+enum Operation<T> {
+    /// The first `usize` values of the stream
+    Take(usize, Stream<T>),
+    /// The values of the first stream followed by those of the second
+    Append(Stream<T>, Stream<T>),
+    /// The values after the first `usize` of the stream, in reverse, which is computed
+    /// in one go when the first of them is needed
+    ReverseAfter(usize, Stream<T>),
+}
+
+impl<T> Stream<T> {
+    fn empty() -> Stream<T> {
+        Stream(None)
+    }
+
+    fn cons(value: T, next: Stream<T>) -> Stream<T> {
+        Stream(Some(Rc::new(Lazy {
+            pending: Cell::new(None),
+            forced: OnceCell::from(Some(Node { value, next })),
+        })))
+    }
+
+    fn suspend(operation: Operation<T>, clone: fn(&T) -> T) -> Stream<T> {
+        Stream(Some(Rc::new(Lazy {
+            pending: Cell::new(Some(Suspension { operation, clone })),
+            forced: OnceCell::new(),
+        })))
+    }
+
+    /// Builds a stream whose first node holds the first of `values`
+    fn from_values(values: Vec<T>) -> Stream<T> {
+        values
+            .into_iter()
+            .rev()
+            .fold(Stream::empty(), |next, value| Stream::cons(value, next))
+    }
+
+    /// The first node, computing it if no version has done so before
+    fn force(&self) -> Option<&Node<T>> {
+        let lazy = self.0.as_deref()?;
+        lazy.forced
+            .get_or_init(|| {
+                let suspension = lazy.pending.take().expect("an unforced cell is suspended");
+                suspension.evaluate()
+            })
+            .as_ref()
+    }
+}
+
+impl<T> Suspension<T> {
+    fn evaluate(self) -> Option<Node<T>> {
+        let clone = self.clone;
+        match self.operation {
+            Operation::Take(n, stream) => {
+                let node = stream.force().filter(|_| n > 0)?;
+                let next = if n == 1 {
+                    Stream::empty()
+                } else {
+                    Stream::suspend(Operation::Take(n - 1, node.next.clone()), clone)
+                };
+
+                Some(Node {
+                    value: clone(&node.value),
+                    next,
+                })
+            }
+            Operation::Append(first, second) => match first.force() {
+                Some(node) => Some(Node {
+                    value: clone(&node.value),
+                    next: Stream::suspend(Operation::Append(node.next.clone(), second), clone),
+                }),
+                None => second.force().map(|node| Node {
+                    value: clone(&node.value),
+                    next: node.next.clone(),
+                }),
+            },
+            Operation::ReverseAfter(n, stream) => {
+                let mut node = stream.force();
+                for _ in 0..n {
+                    node = node?.next.force();
+                }
+
+                let mut reversed = Stream::empty();
+                while let Some(current) = node {
+                    reversed = Stream::cons(clone(&current.value), reversed);
+                    node = current.next.force();
+                }
+
+                // the reversed stream was just built, so nothing else holds its first cell
+                let lazy = Rc::into_inner(reversed.0.take()?).expect("a new cell is not shared");
+                lazy.forced.into_inner().flatten()
+            }
+        }
+    }
+}
+
+impl<T> Clone for Stream<T> {
+    fn clone(&self) -> Self {
+        Stream(self.0.clone())
+    }
+}
+
+impl<T> Drop for Stream<T> {
+    /// Drops the cells that are not shared with another version, without recursing
+    fn drop(&mut self) {
+        let mut branches = Vec::new();
+        let mut next = self.0.take();
+        while let Some(rc) = next.take().or_else(|| branches.pop()) {
+            let Some(lazy) = Rc::into_inner(rc) else {
+                continue;
+            };
+
+            if let Some(suspension) = lazy.pending.into_inner() {
+                match suspension.operation {
+                    Operation::Take(_, mut stream) | Operation::ReverseAfter(_, mut stream) => {
+                        branches.extend(stream.0.take());
+                    }
+                    Operation::Append(mut first, mut second) => {
+                        branches.extend(first.0.take());
+                        branches.extend(second.0.take());
+                    }
+                }
+            }
+            if let Some(Some(mut node)) = lazy.forced.into_inner() {
+                next = node.next.0.take();
+            }
+        }
+    }
+}
+
+/// An immutable deque, where every push or pop returns a new version and leaves the
+/// old one untouched.
+///
+/// This is Okasaki's banker's deque: the values are kept in two lazy lists of shared `Rc`
+/// cells, the front holding the left half from left to right and the rear the right half
+/// from right to left. Versions share every cell they have in common, so keeping old
+/// versions around is cheap.
+///
+/// When one list grows more than three times as long as the other, the deque rebalances
+/// by cutting the long list in half and reversing the cut off half onto the end of the
+/// short one. That work is suspended and only carried out as the values are reached, once
+/// for all versions sharing it, so pushes and pops take amortised O(1), also when old
+/// versions are pushed to or popped from again. The moved values are cloned into new
+/// cells, which is why pushes and pops need `T: Clone`.
+pub struct PersistentDeque<T> {
+    front: Stream<T>,
+    front_len: usize,
+    rear: Stream<T>,
+    rear_len: usize,
+}
+
+impl<T> PersistentDeque<T> {
+    pub fn create() -> PersistentDeque<T> {
+        PersistentDeque {
+            front: Stream::empty(),
+            front_len: 0,
+            rear: Stream::empty(),
+            rear_len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front_len + self.rear_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        // the front is only empty when the rear holds at most one value
+        self.front
+            .force()
+            .or(self.rear.force())
+            .map(|node| &node.value)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.rear
+            .force()
+            .or(self.front.force())
+            .map(|node| &node.value)
+    }
+
+    /// Iterates over the values from left to right
+    ///
+    /// The rear holds its values from right to left, so once the front is done, the nodes
+    /// of the rear are gathered on a stack. This takes O(n) for the whole iteration, and
+    /// O(rear_len) memory once the iterator reaches the rear.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front.force(),
+            rear: self.rear.force(),
+            rear_len: self.rear_len,
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone> PersistentDeque<T> {
+    /// A new version with `x` added to the left
+    pub fn push_left(&self, x: T) -> PersistentDeque<T> {
+        PersistentDeque {
+            front: Stream::cons(x, self.front.clone()),
+            front_len: self.front_len + 1,
+            rear: self.rear.clone(),
+            rear_len: self.rear_len,
+        }
+        .rebalanced()
+    }
+
+    /// A new version with `x` added to the right
+    pub fn push_right(&self, x: T) -> PersistentDeque<T> {
+        PersistentDeque {
+            front: self.front.clone(),
+            front_len: self.front_len,
+            rear: Stream::cons(x, self.rear.clone()),
+            rear_len: self.rear_len + 1,
+        }
+        .rebalanced()
+    }
+
+    /// The leftmost value and a new version without it
+    pub fn pop_left(&self) -> Option<(&T, PersistentDeque<T>)> {
+        match self.front.force() {
+            Some(node) => {
+                let rest = PersistentDeque {
+                    front: node.next.clone(),
+                    front_len: self.front_len - 1,
+                    rear: self.rear.clone(),
+                    rear_len: self.rear_len,
+                };
+                Some((&node.value, rest.rebalanced()))
+            }
+            None => self
+                .rear
+                .force()
+                .map(|node| (&node.value, PersistentDeque::create())),
+        }
+    }
+
+    /// The rightmost value and a new version without it
+    pub fn pop_right(&self) -> Option<(&T, PersistentDeque<T>)> {
+        match self.rear.force() {
+            Some(node) => {
+                let rest = PersistentDeque {
+                    front: self.front.clone(),
+                    front_len: self.front_len,
+                    rear: node.next.clone(),
+                    rear_len: self.rear_len - 1,
+                };
+                Some((&node.value, rest.rebalanced()))
+            }
+            None => self
+                .front
+                .force()
+                .map(|node| (&node.value, PersistentDeque::create())),
+        }
+    }
+
+    /// Suspends moving values between the lists when one has grown too long compared to
+    /// the other
+    fn rebalanced(self) -> PersistentDeque<T> {
+        let len = self.len();
+        let clone: fn(&T) -> T = T::clone;
+        if self.front_len > BALANCE * self.rear_len + 1 {
+            let kept = len.div_ceil(2);
+            // the moved values end up at the far end of the rear, in reverse
+            let moved = Stream::suspend(Operation::ReverseAfter(kept, self.front.clone()), clone);
+
+            PersistentDeque {
+                front: Stream::suspend(Operation::Take(kept, self.front.clone()), clone),
+                front_len: kept,
+                rear: Stream::suspend(Operation::Append(self.rear.clone(), moved), clone),
+                rear_len: len - kept,
+            }
+        } else if self.rear_len > BALANCE * self.front_len + 1 {
+            let kept = len.div_ceil(2);
+            let moved = Stream::suspend(Operation::ReverseAfter(kept, self.rear.clone()), clone);
+
+            PersistentDeque {
+                front: Stream::suspend(Operation::Append(self.front.clone(), moved), clone),
+                front_len: len - kept,
+                rear: Stream::suspend(Operation::Take(kept, self.rear.clone()), clone),
+                rear_len: kept,
+            }
+        } else {
+            self
+        }
+    }
+}
+
+impl<T> Clone for PersistentDeque<T> {
+    /// Shares every cell with the original, in O(1)
+    fn clone(&self) -> Self {
+        PersistentDeque {
+            front: self.front.clone(),
+            front_len: self.front_len,
+            rear: self.rear.clone(),
+            rear_len: self.rear_len,
+        }
+    }
+}
+
+impl<T> Default for PersistentDeque<T> {
+    fn default() -> Self {
+        PersistentDeque::create()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PersistentDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for PersistentDeque<T> {}
+
+impl<T> FromIterator<T> for PersistentDeque<T> {
+    /// Splits the values evenly between both lists
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut front: Vec<T> = iter.into_iter().collect();
+        let mut rear = front.split_off(front.len() / 2);
+        rear.reverse();
+
+        PersistentDeque {
+            front_len: front.len(),
+            rear_len: rear.len(),
+            front: Stream::from_values(front),
+            rear: Stream::from_values(rear),
+        }
+    }
+}
+
+/// Iterates over the values of a [PersistentDeque] from left to right
+pub struct Iter<'a, T> {
+    front: Option<&'a Node<T>>,
+    // the rear until the front is done, after which its nodes are on the stack, with the
+    // value that comes first on top
+    rear: Option<&'a Node<T>>,
+    rear_len: usize,
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(node) = self.front {
+            self.front = node.next.force();
+            return Some(&node.value);
+        }
+
+        if let Some(mut node) = self.rear.take() {
+            self.stack.reserve_exact(self.rear_len);
+            self.stack.push(node);
+            for _ in 1..self.rear_len {
+                node = node.next.force()?;
+                self.stack.push(node);
+            }
+        }
+
+        self.stack.pop().map(|node| &node.value)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::persistent::{PersistentDeque, BALANCE};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        fn contents(deque: &PersistentDeque<usize>) -> Vec<usize> {
+            deque.iter().copied().collect()
+        }
+
+        #[test]
+        fn pushing_leaves_the_old_version_untouched() {
+            let empty = PersistentDeque::create();
+            let one = empty.push_right(1);
+            let two = one.push_right(2);
+            let other = one.push_left(0);
+
+            assert!(empty.is_empty());
+            assert_eq!(contents(&one), vec![1]);
+            assert_eq!(contents(&two), vec![1, 2]);
+            assert_eq!(contents(&other), vec![0, 1]);
+        }
+
+        #[test]
+        fn popping_leaves_the_old_version_untouched() {
+            let data: PersistentDeque<usize> = (0..10).collect();
+            let (first, rest) = data.pop_left().unwrap();
+            let (last, rest) = rest.pop_right().unwrap();
+
+            assert_eq!((*first, *last), (0, 9));
+            assert_eq!(contents(&rest), (1..9).collect::<Vec<_>>());
+            assert_eq!(contents(&data), (0..10).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn popping_from_one_end_empties_the_other() {
+            let mut data = PersistentDeque::create();
+            for x in 0..20 {
+                data = data.push_right(x);
+            }
+
+            let mut popped = vec![];
+            while let Some((x, rest)) = data.pop_left() {
+                popped.push(*x);
+                data = rest;
+            }
+
+            assert_eq!(popped, (0..20).collect::<Vec<_>>());
+            assert_eq!(data.pop_right().map(|(x, _)| *x), None);
+        }
+
+        #[test]
+        fn the_ends_of_a_single_value() {
+            let data = PersistentDeque::create().push_left(5);
+
+            assert_eq!(data.first(), Some(&5));
+            assert_eq!(data.last(), Some(&5));
+            assert_eq!(
+                data.pop_right().map(|(x, rest)| (*x, rest.len())),
+                Some((5, 0))
+            );
+        }
+
+        #[test]
+        fn undoing_returns_to_a_snapshot() {
+            let mut history = vec![PersistentDeque::create()];
+            for x in 0..5 {
+                let next = history.last().unwrap().push_left(x);
+                history.push(next);
+            }
+
+            history.truncate(3);
+            assert_eq!(contents(history.last().unwrap()), vec![1, 0]);
+        }
+
+        #[test]
+        fn clones_share_their_nodes() {
+            let data: PersistentDeque<String> = ["a", "b"].into_iter().map(String::from).collect();
+            let copy = data.clone();
+
+            assert!(std::ptr::eq(data.first().unwrap(), copy.first().unwrap()));
+            assert_eq!(format!("{:?}", copy), r#"["a", "b"]"#);
+        }
+
+        /// Counts how often any of its clones was cloned
+        struct Counted(Rc<Cell<usize>>);
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Counted(Rc::clone(&self.0))
+            }
+        }
+
+        #[test]
+        fn popping_an_old_version_again_does_not_repeat_the_rebalance() {
+            let clones = Rc::new(Cell::new(0));
+            let mut data: PersistentDeque<Counted> =
+                (0..1_000).map(|_| Counted(Rc::clone(&clones))).collect();
+            // stops right before the pop that makes the rear too long
+            while data.rear_len <= BALANCE * (data.front_len - 1) + 1 {
+                data = data.pop_left().unwrap().1;
+            }
+
+            clones.set(0);
+            for _ in 0..100 {
+                let rest = data.pop_left().unwrap().1;
+                assert!(rest.first().is_some());
+                assert_eq!(rest.len(), data.len() - 1);
+            }
+
+            // rebalancing eagerly would copy hundreds of values on every pop
+            assert!(clones.get() < 1_000, "cloned {} values", clones.get());
+        }
+
+        #[test]
+        fn iterating_walks_a_long_rear_in_order() {
+            let mut data = PersistentDeque::create();
+            for x in 0..1_000 {
+                data = data.push_right(x);
+            }
+
+            assert!(data.rear_len > 1);
+            assert!(data.iter().copied().eq(0..1_000));
+        }
+
+        #[test]
+        fn dropping_a_long_deque_does_not_overflow_the_stack() {
+            let data: PersistentDeque<usize> = (0..1_000_000).collect();
+            let shorter = data.pop_left().unwrap().1;
+
+            drop(data);
+            assert_eq!(shorter.len(), 999_999);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::persistent::PersistentDeque;
+        use proptest::prelude::*;
+        use std::collections::VecDeque;
+
+        #[derive(Debug, Clone)]
+        enum Operation {
+            PushLeft(u8),
+            PushRight(u8),
+            PopLeft,
+            PopRight,
+        }
+
+        fn operation() -> impl Strategy<Value = Operation> {
+            prop_oneof![
+                any::<u8>().prop_map(Operation::PushLeft),
+                any::<u8>().prop_map(Operation::PushRight),
+                Just(Operation::PopLeft),
+                Just(Operation::PopRight),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn every_version_stays_as_it_was(operations in prop::collection::vec((operation(), any::<prop::sample::Index>()), 0..200)) {
+                let mut versions = vec![(PersistentDeque::create(), VecDeque::new())];
+                for (operation, version) in operations {
+                    // branch off an arbitrary earlier version, not just the latest one
+                    let (data, expected) = &versions[version.index(versions.len())];
+                    let mut expected = expected.clone();
+                    let data = match operation {
+                        Operation::PushLeft(x) => {
+                            expected.push_front(x);
+                            data.push_left(x)
+                        }
+                        Operation::PushRight(x) => {
+                            expected.push_back(x);
+                            data.push_right(x)
+                        }
+                        Operation::PopLeft => match data.pop_left() {
+                            Some((x, rest)) => {
+                                prop_assert_eq!(Some(*x), expected.pop_front());
+                                rest
+                            }
+                            None => data.clone(),
+                        },
+                        Operation::PopRight => match data.pop_right() {
+                            Some((x, rest)) => {
+                                prop_assert_eq!(Some(*x), expected.pop_back());
+                                rest
+                            }
+                            None => data.clone(),
+                        },
+                    };
+                    versions.push((data, expected));
+                }
+
+                for (data, expected) in &versions {
+                    prop_assert_eq!(data.len(), expected.len());
+                    prop_assert_eq!(data.first(), expected.front());
+                    prop_assert_eq!(data.last(), expected.back());
+                    prop_assert!(data.iter().eq(expected.iter()));
+                }
+            }
+        }
+    }
+}
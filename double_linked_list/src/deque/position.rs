@@ -0,0 +1,183 @@
+use crate::deque::handle::NodeHandle;
+use crate::deque::item::Item;
+use crate::deque::Deque;
+
+pub(in crate::deque) mod sealed {
+    use crate::deque::item::Item;
+    use crate::deque::Deque;
+
+    pub trait Locate {
+        /// Finds the item in the deque, or returns a null pointer when it is not there
+        fn locate<T>(&self, deque: &Deque<T>) -> *mut Item<T>;
+    }
+}
+
+/// Identifies an item of a deque, either by index or by [NodeHandle].
+///
+/// An index is found by walking from whichever end is closest, in O(min(n, len - n)),
+/// while a handle is found in O(1).
+pub trait Position: sealed::Locate {}
+
+impl sealed::Locate for usize {
+    fn locate<T>(&self, deque: &Deque<T>) -> *mut Item<T> {
+        deque.item_ptr_at(*self)
+    }
+}
+
+impl Position for usize {}
+
+impl<T> Deque<T> {
+    pub fn get<P: Position>(&self, position: P) -> Option<&T> {
+        unsafe { position.locate(self).as_ref() }.map(|item| &item.value)
+    }
+
+    pub fn get_mut<P: Position>(&mut self, position: P) -> Option<&mut T> {
+        unsafe { position.locate(self).as_mut() }.map(|item| &mut item.value)
+    }
+
+    /// Removes an item, returning its value
+    pub fn remove<P: Position>(&mut self, position: P) -> Option<T> {
+        let ptr = position.locate(self);
+        if ptr.is_null() {
+            None
+        } else {
+            unsafe {
+                self.unlink(ptr);
+                Some(self.release(ptr))
+            }
+        }
+    }
+
+    /// Inserts a value so that it ends up at the given index, shifting later items right
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is larger than the length of the deque.
+    pub fn insert(&mut self, index: usize, x: T) -> NodeHandle {
+        assert!(
+            index <= self.len,
+            "Cannot insert at {} in a deque of length {}",
+            index,
+            self.len
+        );
+
+        let (left_ptr, right_ptr) = if index == self.len {
+            (self.last, std::ptr::null_mut())
+        } else {
+            let ptr = self.item_ptr_at(index);
+            (unsafe { (*ptr).left_ptr }, ptr)
+        };
+
+        let created = self.allocate(x);
+        unsafe { self.link_between(created, left_ptr, right_ptr) };
+
+        self.register(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::Deque;
+
+        #[test]
+        fn values_can_be_read_by_index() {
+            let data = deque![1, 2, 3, 4, 5];
+
+            assert_eq!(data.get(0), Some(&1));
+            assert_eq!(data.get(1), Some(&2));
+            assert_eq!(data.get(3), Some(&4));
+            assert_eq!(data.get(4), Some(&5));
+            assert_eq!(data.get(5), None);
+        }
+
+        #[test]
+        fn values_can_be_changed_by_index() {
+            let mut data = deque![1, 2, 3];
+            *data.get_mut(2).unwrap() = 30;
+
+            assert_eq!(data, deque![1, 2, 30]);
+            assert_eq!(data.get_mut(3), None);
+        }
+
+        #[test]
+        fn values_can_be_removed_by_index() {
+            let mut data = deque![1, 2, 3];
+
+            assert_eq!(data.remove(1), Some(2));
+            assert_eq!(data.remove(2), None);
+            assert_eq!(data.remove(1), Some(3));
+            assert_eq!(data, deque![1]);
+            assert_eq!(data.last().unwrap().value(), &1);
+        }
+
+        #[test]
+        fn removing_by_index_invalidates_the_handle() {
+            let mut data = Deque::create();
+            data.push_right(1);
+            let handle = data.push_right(2);
+            data.remove(1);
+
+            assert_eq!(data.get(handle), None);
+        }
+
+        #[test]
+        fn values_can_be_inserted_at_an_index() {
+            let mut data = deque![1, 3];
+            data.insert(1, 2);
+            data.insert(0, 0);
+            let handle = data.insert(4, 4);
+
+            assert_eq!(data, deque![0, 1, 2, 3, 4]);
+            assert_eq!(data.get(handle), Some(&4));
+            assert_eq!(data.last().unwrap().value(), &4);
+        }
+
+        #[test]
+        fn inserting_into_an_empty_deque() {
+            let mut data = Deque::create();
+            data.insert(0, 1);
+
+            assert_eq!(data, deque![1]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn inserting_beyond_the_end() {
+            let mut data = deque![1];
+            data.insert(2, 1);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::Deque;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn reading_by_index_matches_iterating(data: Deque<usize>) {
+                for (index, value) in data.iter().enumerate() {
+                    prop_assert_eq!(data.get(index), Some(value));
+                }
+                prop_assert_eq!(data.get(data.len()), None);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn inserting_and_removing_matches_a_vec(mut data: Deque<usize>, index: prop::sample::Index, value: usize) {
+                let mut expected: Vec<usize> = data.iter().cloned().collect();
+                let index = index.index(data.len() + 1);
+
+                expected.insert(index, value);
+                data.insert(index, value);
+                prop_assert_eq!(data.iter().cloned().collect::<Vec<_>>(), expected.clone());
+
+                prop_assert_eq!(data.remove(index), Some(expected.remove(index)));
+                prop_assert_eq!(data.iter().rev().cloned().collect::<Vec<_>>(), expected.into_iter().rev().collect::<Vec<_>>());
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use crate::deque::Deque;
+
+impl<T> Deque<T> {
+    /// Reverses the order of the items in place by swapping their links
+    pub fn reverse(&mut self) {
+        let mut current = self.first;
+        while !current.is_null() {
+            let item = unsafe { &mut *current };
+            std::mem::swap(&mut item.left_ptr, &mut item.right_ptr);
+
+            // the old right neighbour is now on the left
+            current = item.left_ptr;
+        }
+
+        std::mem::swap(&mut self.first, &mut self.last);
+    }
+
+    /// Rotates the deque `n` places to the left, so that the item at index `n` becomes first
+    ///
+    /// Only the items at the new ends are relinked, after walking O(min(n, len - n)) items.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `n` is larger than the length of the deque.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate by {} in a deque of length {}",
+            n,
+            self.len
+        );
+
+        if n == 0 || n == self.len {
+            return;
+        }
+
+        let new_first = self.item_ptr_at(n);
+        unsafe {
+            let new_last = (*new_first).left_ptr;
+
+            // close the deque into a ring, then open it up at the new ends
+            (*self.last).right_ptr = self.first;
+            (*self.first).left_ptr = self.last;
+            (*new_last).right_ptr = std::ptr::null_mut();
+            (*new_first).left_ptr = std::ptr::null_mut();
+
+            self.first = new_first;
+            self.last = new_last;
+        }
+    }
+
+    /// Rotates the deque `n` places to the right, so that the first item ends up at index `n`
+    ///
+    /// # Panics
+    ///
+    /// Panics when `n` is larger than the length of the deque.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate by {} in a deque of length {}",
+            n,
+            self.len
+        );
+
+        self.rotate_left(self.len - n);
+    }
+
+    /// Removes consecutive repeated values, keeping the first of each run
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        if self.first.is_null() {
+            return;
+        }
+
+        let mut kept = self.first;
+        let mut current = unsafe { (*kept).right_ptr };
+        while !current.is_null() {
+            unsafe {
+                let next = (*current).right_ptr;
+                if (*current).value == (*kept).value {
+                    self.unlink(current);
+                    drop(self.release(current));
+                } else {
+                    kept = current;
+                }
+                current = next;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::Deque;
+
+        #[test]
+        fn reversing_swaps_the_ends() {
+            let mut data = deque![1, 2, 3];
+            data.reverse();
+
+            assert_eq!(data, deque![3, 2, 1]);
+            assert_eq!(data.first().unwrap().left(), None);
+            assert_eq!(data.first().unwrap().right().unwrap().value(), &2);
+            assert_eq!(data.last().unwrap().value(), &1);
+        }
+
+        #[test]
+        fn reversing_an_empty_deque() {
+            let mut data: Deque<usize> = deque![];
+            data.reverse();
+
+            assert_eq!(data, deque![]);
+        }
+
+        #[test]
+        fn rotating_left() {
+            let mut data = deque![1, 2, 3, 4, 5];
+            data.rotate_left(2);
+
+            assert_eq!(data, deque![3, 4, 5, 1, 2]);
+            assert_eq!(data.first().unwrap().left(), None);
+            assert_eq!(data.last().unwrap().right(), None);
+        }
+
+        #[test]
+        fn rotating_right() {
+            let mut data = deque![1, 2, 3, 4, 5];
+            data.rotate_right(1);
+
+            assert_eq!(data, deque![5, 1, 2, 3, 4]);
+        }
+
+        #[test]
+        fn rotating_by_the_length_changes_nothing() {
+            let mut data = deque![1, 2];
+            data.rotate_left(2);
+            data.rotate_right(0);
+
+            assert_eq!(data, deque![1, 2]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rotating_beyond_the_length() {
+            let mut data = deque![1, 2];
+            data.rotate_left(3);
+        }
+
+        #[test]
+        fn deduplicating_removes_consecutive_repeats() {
+            let mut data = deque![1, 1, 2, 3, 3, 3, 1];
+            data.dedup();
+
+            assert_eq!(data, deque![1, 2, 3, 1]);
+            assert_eq!(data.len(), 4);
+        }
+
+        #[test]
+        fn deduplicating_owned_values() {
+            let mut data = deque![String::from("a"), String::from("a")];
+            data.dedup();
+
+            assert_eq!(data, deque![String::from("a")]);
+            assert_eq!(data.last().unwrap().value(), "a");
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::Deque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn reversing_matches_a_vec(mut data: Deque<usize>) {
+                let mut expected = values(&data);
+                expected.reverse();
+                data.reverse();
+
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn rotating_matches_a_vec(mut data: Deque<usize>, n: prop::sample::Index) {
+                let n = n.index(data.len() + 1);
+                let mut expected = values(&data);
+                expected.rotate_left(n);
+                data.rotate_left(n);
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn deduplicating_matches_a_vec(original: Vec<u8>) {
+                let mut data = Deque::create();
+                for x in original.iter() {
+                    data.push_right(*x as usize);
+                }
+                let mut expected: Vec<usize> = original.iter().map(|x| *x as usize).collect();
+                expected.dedup();
+                data.dedup();
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::deque::item::Item;
+use crate::deque::Deque;
+
+/// Removes and yields the values matching a predicate, see [Deque::extract_if]
+pub struct ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    deque: &'a mut Deque<T>,
+    current: *mut Item<T>,
+    predicate: F,
+}
+
+impl<T> Deque<T> {
+    /// Keeps only the values matching the predicate, unlinking the others in a single pass
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.extract_if(|value| !predicate(value)).for_each(drop);
+    }
+
+    /// Lazily removes the values matching the predicate, yielding them from left to right
+    ///
+    /// Values that are not reached, because the iterator is dropped early, stay in the deque.
+    pub fn extract_if<F>(&mut self, predicate: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            current: self.first,
+            deque: self,
+            predicate,
+        }
+    }
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.current.is_null() {
+            let ptr = self.current;
+            unsafe {
+                self.current = (*ptr).right_ptr;
+                if (self.predicate)(&mut (*ptr).value) {
+                    self.deque.unlink(ptr);
+                    return Some(self.deque.release(ptr));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.deque.len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::Deque;
+
+        #[test]
+        fn retaining_removes_the_other_values() {
+            let mut data = deque![1, 2, 3, 4, 5, 6];
+            data.retain(|x| x % 2 == 0);
+
+            assert_eq!(data, deque![2, 4, 6]);
+            assert_eq!(data.len(), 3);
+            assert_eq!(data.first().unwrap().value(), &2);
+            assert_eq!(data.last().unwrap().value(), &6);
+        }
+
+        #[test]
+        fn retaining_nothing_empties_the_deque() {
+            let mut data = deque![String::from("a"), String::from("b")];
+            data.retain(|_| false);
+
+            assert!(data.is_empty());
+            assert_eq!(data.first(), None);
+        }
+
+        #[test]
+        fn extracted_values_are_yielded_in_order() {
+            let mut data = deque![1, 2, 3, 4, 5];
+            let extracted: Vec<_> = data.extract_if(|x| *x > 2).collect();
+
+            assert_eq!(extracted, vec![3, 4, 5]);
+            assert_eq!(data, deque![1, 2]);
+            assert_eq!(data.last().unwrap().right(), None);
+        }
+
+        #[test]
+        fn values_can_be_changed_while_extracting() {
+            let mut data = deque![1, 2, 3];
+            let extracted: Vec<_> = data
+                .extract_if(|x| {
+                    *x *= 10;
+                    *x == 20
+                })
+                .collect();
+
+            assert_eq!(extracted, vec![20]);
+            assert_eq!(data, deque![10, 30]);
+        }
+
+        #[test]
+        fn unreached_values_stay_when_extraction_stops_early() {
+            let mut data = deque![1, 2, 3, 4];
+            let first = data.extract_if(|x| *x % 2 == 0).next();
+
+            assert_eq!(first, Some(2));
+            assert_eq!(data, deque![1, 3, 4]);
+        }
+
+        #[test]
+        fn handles_of_extracted_values_are_invalidated() {
+            let mut data = Deque::create();
+            let one = data.push_right(1);
+            let two = data.push_right(2);
+            data.retain(|x| *x == 1);
+
+            assert_eq!(data.get(one), Some(&1));
+            assert_eq!(data.get(two), None);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::Deque;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn retaining_matches_a_vec(mut data: Deque<usize>) {
+                let mut expected: Vec<usize> = data.iter().cloned().collect();
+                expected.retain(|x| x % 3 != 0);
+                data.retain(|x| x % 3 != 0);
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(data.iter().cloned().collect::<Vec<_>>(), expected.clone());
+                prop_assert_eq!(data.iter().rev().cloned().collect::<Vec<_>>(), expected.into_iter().rev().collect::<Vec<_>>());
+            }
+        }
+    }
+}
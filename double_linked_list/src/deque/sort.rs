@@ -0,0 +1,459 @@
+use crate::deque::handle::next_id;
+use crate::deque::item::Item;
+use crate::deque::Deque;
+use std::cmp::Ordering;
+
+impl<T> Deque<T> {
+    /// Sorts the deque in place, keeping equal values in their original order
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Sorts the deque in place with a stable, bottom-up merge sort
+    ///
+    /// The items are relinked rather than moved, so sorting takes O(n log n) comparisons
+    /// and no allocations. Should the comparison panic, every item stays in the deque, in
+    /// an unspecified order.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len < 2 {
+            return;
+        }
+
+        let list = std::mem::replace(&mut self.first, std::ptr::null_mut());
+        let mut runs = Runs::start(self, list);
+
+        // merges runs of `size` items pairwise, doubling `size` until a single run remains
+        let mut size = 1;
+        loop {
+            let mut merges = 0;
+
+            unsafe {
+                while !runs.q.is_null() {
+                    merges += 1;
+
+                    // the second run starts `size` items after the first one
+                    runs.p = runs.q;
+                    runs.p_size = 0;
+                    while runs.p_size < size && !runs.q.is_null() {
+                        runs.p_size += 1;
+                        runs.q = (*runs.q).right_ptr;
+                    }
+                    let mut q_size = size;
+
+                    while runs.p_size > 0 || (q_size > 0 && !runs.q.is_null()) {
+                        // taking from the first run on equality keeps the sort stable
+                        let take_p = if runs.p_size == 0 {
+                            false
+                        } else if q_size == 0 || runs.q.is_null() {
+                            true
+                        } else {
+                            compare(&(*runs.p).value, &(*runs.q).value) != Ordering::Greater
+                        };
+
+                        if take_p {
+                            runs.take_p();
+                        } else {
+                            runs.take_q();
+                            q_size -= 1;
+                        }
+                    }
+                }
+            }
+
+            if merges <= 1 {
+                break;
+            }
+            size *= 2;
+            runs.restart();
+        }
+
+        // dropping the runs links the sorted items back into the deque
+    }
+
+    /// Merges another sorted deque into this sorted deque, leaving `other` empty
+    ///
+    /// The items are relinked rather than moved, so merging takes O(n + m) comparisons and
+    /// no allocations. Values of this deque come before equal values of `other`. Should the
+    /// comparison panic, the items of both deques end up in this deque, in an unspecified
+    /// order.
+    pub fn merge(&mut self, other: &mut Deque<T>)
+    where
+        T: Ord,
+    {
+        let mut other = std::mem::take(other);
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            unsafe { self.splice_between(other, std::ptr::null_mut(), std::ptr::null_mut()) };
+            return;
+        }
+
+        // the items of this deque form the first run and those of `other` the second
+        let a = std::mem::replace(&mut self.first, std::ptr::null_mut());
+        let a_len = self.len;
+        let b = std::mem::replace(&mut other.first, std::ptr::null_mut());
+        let b_len = std::mem::replace(&mut other.len, 0);
+        other.last = std::ptr::null_mut();
+
+        let mut runs = Runs::start(self, b);
+        runs.len += b_len;
+        runs.p = a;
+        runs.p_size = a_len;
+        unsafe {
+            while runs.p_size > 0 && !runs.q.is_null() {
+                if (*runs.p).value <= (*runs.q).value {
+                    runs.take_p();
+                } else {
+                    runs.take_q();
+                }
+            }
+        }
+
+        // dropping the runs links whatever remains of either deque after the merged items
+    }
+}
+
+/// The items of a deque while they are being merged
+///
+/// The deque is emptied while the links are inconsistent. Dropping the runs links the
+/// merged items, the rest of the first run and the items from the second run onwards back
+/// into the deque, so a panicking comparison cannot lose or expose any of them.
+struct Runs<'a, T> {
+    deque: &'a mut Deque<T>,
+    len: usize,
+    id: u64,
+    // the merged items, linked in both directions
+    merged: *mut Item<T>,
+    tail: *mut Item<T>,
+    // the items of the first run that are left to merge
+    p: *mut Item<T>,
+    p_size: usize,
+    // the rest of the second run, followed by the runs that are still to merge
+    q: *mut Item<T>,
+}
+
+impl<'a, T> Runs<'a, T> {
+    /// Takes the items of the deque, starting at `list`, out of it
+    fn start(deque: &'a mut Deque<T>, list: *mut Item<T>) -> Runs<'a, T> {
+        deque.first = std::ptr::null_mut();
+        deque.last = std::ptr::null_mut();
+        let len = std::mem::replace(&mut deque.len, 0);
+        // handles are rejected while merging, as the deque is empty in the meantime
+        let id = std::mem::replace(&mut deque.id, next_id());
+
+        Runs {
+            deque,
+            len,
+            id,
+            merged: std::ptr::null_mut(),
+            tail: std::ptr::null_mut(),
+            p: std::ptr::null_mut(),
+            p_size: 0,
+            q: list,
+        }
+    }
+
+    /// Starts another pass over the merged items
+    fn restart(&mut self) {
+        unsafe { (*self.tail).right_ptr = std::ptr::null_mut() };
+        self.q = std::mem::replace(&mut self.merged, std::ptr::null_mut());
+        self.tail = std::ptr::null_mut();
+    }
+
+    unsafe fn take_p(&mut self) {
+        let next = self.p;
+        self.p = (*next).right_ptr;
+        self.p_size -= 1;
+        self.push(next);
+    }
+
+    unsafe fn take_q(&mut self) {
+        let next = self.q;
+        self.q = (*next).right_ptr;
+        self.push(next);
+    }
+
+    unsafe fn push(&mut self, next: *mut Item<T>) {
+        if self.tail.is_null() {
+            self.merged = next;
+        } else {
+            (*self.tail).right_ptr = next;
+        }
+        (*next).left_ptr = self.tail;
+        self.tail = next;
+    }
+}
+
+impl<T> Drop for Runs<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            // the rest of the first run continues into the rest of the second one
+            let mut rest = self.q;
+            if self.p_size > 0 {
+                let mut last = self.p;
+                for _ in 1..self.p_size {
+                    last = (*last).right_ptr;
+                }
+                (*last).right_ptr = self.q;
+                rest = self.p;
+            }
+
+            if self.tail.is_null() {
+                self.merged = rest;
+            } else {
+                (*self.tail).right_ptr = rest;
+            }
+
+            // only the items that were not merged still need their left links fixed
+            let mut left = self.tail;
+            let mut current = rest;
+            while !current.is_null() {
+                (*current).left_ptr = left;
+                left = current;
+                current = (*current).right_ptr;
+            }
+
+            self.deque.first = self.merged;
+            self.deque.last = left;
+        }
+        self.deque.len = self.len;
+        self.deque.id = self.id;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::Deque;
+        use crate::test_helpers::{values, values_reversed};
+
+        #[test]
+        fn sorting_an_empty_deque() {
+            let mut data: Deque<usize> = deque![];
+            data.sort();
+
+            assert_eq!(data, deque![]);
+        }
+
+        #[test]
+        fn sorting_relinks_both_directions() {
+            let mut data = deque![5, 3, 1, 4, 2];
+            data.sort();
+
+            assert_eq!(data, deque![1, 2, 3, 4, 5]);
+            assert_eq!(
+                data.iter().rev().cloned().collect::<Vec<_>>(),
+                vec![5, 4, 3, 2, 1]
+            );
+            assert_eq!(data.first().unwrap().left(), None);
+            assert_eq!(data.last().unwrap().right(), None);
+        }
+
+        #[test]
+        fn sorting_is_stable() {
+            let mut data = deque![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+            data.sort_by_key(|x| x.0);
+
+            assert_eq!(data, deque![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+        }
+
+        #[test]
+        fn sorting_in_reverse() {
+            let mut data = deque![1, 3, 2];
+            data.sort_by(|a, b| b.cmp(a));
+
+            assert_eq!(data, deque![3, 2, 1]);
+        }
+
+        #[test]
+        fn handles_survive_sorting() {
+            let mut data = Deque::create();
+            let three = data.push_right(3);
+            data.push_right(1);
+            data.sort();
+
+            assert_eq!(data.remove(three), Some(3));
+            assert_eq!(data, deque![1]);
+        }
+
+        #[test]
+        fn a_panicking_comparison_keeps_every_value() {
+            let mut data: Deque<String> = (0..20).map(|x| x.to_string()).collect();
+            let handle = data.push_right(String::from("20"));
+            let mut comparisons = 0;
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                data.sort_by(|a, b| {
+                    comparisons += 1;
+                    if comparisons == 30 {
+                        panic!("cannot compare");
+                    }
+                    a.cmp(b)
+                });
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(data.len(), 21);
+            let mut sorted = values(&data);
+            assert_eq!(values_reversed(&data), sorted);
+            sorted.sort_by_key(|x| x.parse::<usize>().unwrap());
+            assert_eq!(sorted, (0..21).map(|x| x.to_string()).collect::<Vec<_>>());
+            assert_eq!(data.get(handle).map(String::as_str), Some("20"));
+        }
+
+        #[test]
+        fn a_panicking_comparison_keeps_the_values_of_both_merged_deques() {
+            #[derive(Debug, PartialEq, Eq)]
+            struct Fragile(usize);
+            impl PartialOrd for Fragile {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+            impl Ord for Fragile {
+                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    if self.0 == 5 || other.0 == 5 {
+                        panic!("cannot compare");
+                    }
+                    self.0.cmp(&other.0)
+                }
+            }
+
+            let mut a: Deque<Fragile> = [0, 2, 4, 6].map(Fragile).into();
+            let mut b: Deque<Fragile> = [1, 3, 5, 7].map(Fragile).into();
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                a.merge(&mut b);
+            }));
+
+            assert!(result.is_err());
+            assert!(b.is_empty());
+            assert_eq!(a.len(), 8);
+            let mut merged: Vec<usize> = a.iter().map(|x| x.0).collect();
+            let reversed: Vec<usize> = a.iter().rev().map(|x| x.0).collect();
+            assert_eq!(reversed.into_iter().rev().collect::<Vec<_>>(), merged);
+            merged.sort();
+            assert_eq!(merged, (0..8).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn merging_sorted_deques() {
+            let mut a = deque![1, 3, 5, 7];
+            let mut b = deque![2, 3, 4];
+            a.merge(&mut b);
+
+            assert_eq!(a, deque![1, 2, 3, 3, 4, 5, 7]);
+            assert_eq!(a.len(), 7);
+            assert_eq!(a.last().unwrap().value(), &7);
+            assert!(b.is_empty());
+        }
+
+        #[test]
+        fn merging_keeps_values_of_self_first() {
+            // only the number takes part in comparisons
+            #[derive(Debug)]
+            struct Keyed(u8, char);
+            impl PartialEq for Keyed {
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+            impl Eq for Keyed {}
+            impl PartialOrd for Keyed {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+            impl Ord for Keyed {
+                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    self.0.cmp(&other.0)
+                }
+            }
+
+            let mut a = deque![Keyed(1, 'a'), Keyed(2, 'a')];
+            let mut b = deque![Keyed(1, 'b'), Keyed(2, 'b')];
+            a.merge(&mut b);
+
+            let order: String = a.iter().map(|x| x.1).collect();
+            assert_eq!(order, "abab");
+        }
+
+        #[test]
+        fn merging_with_empty_deques() {
+            let mut a = deque![1, 2];
+            let mut b: Deque<usize> = deque![];
+            a.merge(&mut b);
+            b.merge(&mut a);
+
+            assert!(a.is_empty());
+            assert_eq!(b, deque![1, 2]);
+        }
+
+        #[test]
+        fn handles_of_merged_items_are_rejected() {
+            let mut a = Deque::create();
+            let mut b = Deque::create();
+            let one = a.push_right(1);
+            let two = b.push_right(2);
+            a.merge(&mut b);
+
+            assert_eq!(a.get(one), Some(&1));
+            assert_eq!(a.get(two), None);
+            assert_eq!(b.get(two), None);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::Deque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn sorting_matches_a_stable_sort(original: Vec<(u8, usize)>) {
+                let mut data: Deque<(u8, usize)> = Deque::create();
+                for x in original.iter() {
+                    data.push_right(*x);
+                }
+                let mut expected = original.clone();
+                expected.sort_by_key(|x| x.0);
+                data.sort_by_key(|x| x.0);
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn merging_matches_sorting_the_concatenation(mut a: Deque<usize>, mut b: Deque<usize>) {
+                a.sort();
+                b.sort();
+                let mut expected = values(&a);
+                expected.extend(values(&b));
+                expected.sort();
+                a.merge(&mut b);
+
+                prop_assert_eq!(a.len(), expected.len());
+                prop_assert_eq!(values(&a), expected.clone());
+                prop_assert_eq!(values_reversed(&a), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use crate::deque::Deque;
+
+impl<T> Deque<T> {
+    /// Moves all items of `other` to the right end of this deque in O(1), leaving `other` empty
+    ///
+    /// The handles of `other` are invalidated, while those of this deque stay valid.
+    pub fn append(&mut self, other: &mut Deque<T>) {
+        let other = other.take_items();
+        unsafe { self.splice_between(other, self.last, std::ptr::null_mut()) };
+    }
+
+    /// Moves all items of `other` to the left end of this deque in O(1), leaving `other` empty
+    ///
+    /// The handles of `other` are invalidated, while those of this deque stay valid.
+    pub fn prepend(&mut self, other: &mut Deque<T>) {
+        let other = other.take_items();
+        unsafe { self.splice_between(other, std::ptr::null_mut(), self.first) };
+    }
+
+    /// Detaches all items into a new deque in O(1), keeping the released items for reuse
+    fn take_items(&mut self) -> Deque<T> {
+        if self.is_empty() {
+            Deque::create()
+        } else {
+            unsafe { self.detach(self.first, self.last, self.len) }
+        }
+    }
+
+    /// Splits the deque in two at an index, returning the items from that index onwards
+    ///
+    /// Finding the index walks from the closest end, so this takes O(min(at, len - at)).
+    /// The handles of the items split off are invalidated, even when `at` is 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `at` is larger than the length of the deque.
+    pub fn split_off(&mut self, at: usize) -> Deque<T> {
+        assert!(
+            at <= self.len,
+            "Cannot split off at {} in a deque of length {}",
+            at,
+            self.len
+        );
+
+        if at == 0 {
+            self.take_items()
+        } else if at == self.len {
+            Deque::create()
+        } else {
+            let ptr = self.item_ptr_at(at);
+            let len = self.len - at;
+            unsafe { self.detach(ptr, self.last, len) }
+        }
+    }
+
+    /// Inserts all items of `other` at an index, so that its first item ends up at that index
+    ///
+    /// Finding the index walks from the closest end, after which the items are linked in O(1).
+    /// The handles of `other` are invalidated.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `at` is larger than the length of the deque.
+    pub fn splice(&mut self, at: usize, other: Deque<T>) {
+        assert!(
+            at <= self.len,
+            "Cannot splice at {} in a deque of length {}",
+            at,
+            self.len
+        );
+
+        if at == self.len {
+            unsafe { self.splice_between(other, self.last, std::ptr::null_mut()) };
+        } else {
+            let ptr = self.item_ptr_at(at);
+            unsafe { self.splice_between(other, (*ptr).left_ptr, ptr) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque;
+        use crate::deque::Deque;
+
+        #[test]
+        fn appending_moves_all_items_to_the_right_end() {
+            let mut a = deque![1, 2];
+            let mut b = deque![3, 4];
+            a.append(&mut b);
+
+            assert_eq!(a, deque![1, 2, 3, 4]);
+            assert_eq!(a.len(), 4);
+            assert!(b.is_empty());
+            assert_eq!(b.first(), None);
+        }
+
+        #[test]
+        fn prepending_moves_all_items_to_the_left_end() {
+            let mut a = deque![3, 4];
+            let mut b = deque![1, 2];
+            a.prepend(&mut b);
+
+            assert_eq!(a, deque![1, 2, 3, 4]);
+            assert!(b.is_empty());
+        }
+
+        #[test]
+        fn appending_to_an_empty_deque() {
+            let mut a: Deque<usize> = deque![];
+            let mut b = deque![1];
+            a.append(&mut b);
+
+            assert_eq!(a.first().unwrap().value(), &1);
+            assert_eq!(a.last().unwrap().value(), &1);
+        }
+
+        #[test]
+        fn handles_of_appended_items_are_rejected() {
+            let mut a = Deque::create();
+            let mut b = Deque::create();
+            let one = a.push_right(1);
+            let two = b.push_right(2);
+            a.append(&mut b);
+
+            assert_eq!(a.remove(two), None);
+            assert_eq!(a.get(one), Some(&1));
+            assert_eq!(b.get(two), None);
+        }
+
+        #[test]
+        fn handles_of_the_receiving_deque_stay_valid() {
+            let mut a = Deque::create();
+            let one = a.push_right(1);
+            a.prepend(&mut deque![0]);
+            a.append(&mut deque![2]);
+
+            assert_eq!(a.remove(one), Some(1));
+            assert_eq!(a, deque![0, 2]);
+        }
+
+        #[test]
+        fn splitting_off_in_the_middle() {
+            let mut a = deque![1, 2, 3, 4, 5];
+            let b = a.split_off(2);
+
+            assert_eq!(a, deque![1, 2]);
+            assert_eq!(b, deque![3, 4, 5]);
+            assert_eq!(a.last().unwrap().right(), None);
+            assert_eq!(b.first().unwrap().left(), None);
+        }
+
+        #[test]
+        fn splitting_off_at_the_ends() {
+            let mut a = deque![1, 2];
+            assert_eq!(a.split_off(2), deque![]);
+            assert_eq!(a.split_off(0), deque![1, 2]);
+            assert!(a.is_empty());
+        }
+
+        #[test]
+        #[should_panic]
+        fn splitting_off_beyond_the_end() {
+            let mut a = deque![1, 2];
+            a.split_off(3);
+        }
+
+        #[test]
+        fn handles_are_rejected_after_splitting_off() {
+            let mut a = Deque::create();
+            let handles: Vec<_> = (0..6).map(|n| a.push_right(n)).collect();
+            let mut b = a.split_off(1);
+
+            assert_eq!(a.get(handles[0]), None);
+            assert_eq!(a.get(handles[3]), None);
+            assert_eq!(b.remove(handles[3]), None);
+            assert_eq!(b, deque![1, 2, 3, 4, 5]);
+
+            let handle = a.push_right(6);
+            assert_eq!(a.get(handle), Some(&6));
+        }
+
+        #[test]
+        fn handles_are_rejected_after_splitting_off_everything() {
+            let mut a = Deque::create();
+            let handles: Vec<_> = (0..3).map(|n| a.push_right(n)).collect();
+            let mut b = a.split_off(0);
+
+            assert!(a.is_empty());
+            assert_eq!(a.get(handles[0]), None);
+            assert_eq!(b.get(handles[1]), None);
+            assert_eq!(b.remove(handles[2]), None);
+            assert_eq!(b, deque![0, 1, 2]);
+        }
+
+        #[test]
+        fn splicing_at_an_index() {
+            let mut a = deque![1, 4];
+            a.splice(1, deque![2, 3]);
+            a.splice(0, deque![0]);
+            a.splice(5, deque![5]);
+
+            assert_eq!(a, deque![0, 1, 2, 3, 4, 5]);
+            assert_eq!(a.len(), 6);
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::Deque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn appending_concatenates(mut a: Deque<usize>, mut b: Deque<usize>) {
+                let mut expected = values(&a);
+                expected.extend(values(&b));
+                a.append(&mut b);
+
+                prop_assert_eq!(a.len(), expected.len());
+                prop_assert_eq!(values(&a), expected.clone());
+                prop_assert_eq!(values_reversed(&a), expected);
+                prop_assert_eq!(b.len(), 0);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn prepending_concatenates(mut a: Deque<usize>, mut b: Deque<usize>) {
+                let mut expected = values(&b);
+                expected.extend(values(&a));
+                a.prepend(&mut b);
+
+                prop_assert_eq!(a.len(), expected.len());
+                prop_assert_eq!(values(&a), expected.clone());
+                prop_assert_eq!(values_reversed(&a), expected);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn splitting_off_and_appending_restores_the_deque(mut a: Deque<usize>, at: prop::sample::Index) {
+                let original = values(&a);
+                let at = at.index(a.len() + 1);
+                let mut b = a.split_off(at);
+
+                prop_assert_eq!(a.len(), at);
+                prop_assert_eq!(b.len(), original.len() - at);
+                prop_assert_eq!(values(&a), original[..at].to_vec());
+                prop_assert_eq!(values_reversed(&b), original[at..].to_vec());
+
+                a.append(&mut b);
+                prop_assert_eq!(values(&a), original.clone());
+                prop_assert_eq!(values_reversed(&a), original);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn splicing_inserts_at_the_index(mut a: Deque<usize>, b: Deque<usize>, at: prop::sample::Index) {
+                let at = at.index(a.len() + 1);
+                let mut expected = values(&a);
+                let inserted = values(&b);
+                expected.splice(at..at, inserted);
+                a.splice(at, b);
+
+                prop_assert_eq!(a.len(), expected.len());
+                prop_assert_eq!(values(&a), expected.clone());
+                prop_assert_eq!(values_reversed(&a), expected);
+            }
+        }
+    }
+}
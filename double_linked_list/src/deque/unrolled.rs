@@ -0,0 +1,516 @@
+mod chunk;
+pub mod cursor;
+pub mod iterator;
+
+pub use chunk::CHUNK_CAPACITY;
+
+use crate::deque::unrolled::chunk::Chunk;
+use std::fmt::{Debug, Formatter};
+
+/// A double ended queue whose nodes each hold up to [CHUNK_CAPACITY] values.
+///
+/// Keeping several values in one allocation makes pushes, pops and iteration much cheaper
+/// than with a [Deque](crate::deque::Deque), at the cost of moving values around when
+/// inserting or removing in the middle, so values do not have stable addresses.
+pub struct UnrolledDeque<T> {
+    first: *mut Chunk<T>,
+    last: *mut Chunk<T>,
+    len: usize,
+}
+
+// the chunks are owned by the deque and only reachable through it, like a `Deque`
+unsafe impl<T: Send> Send for UnrolledDeque<T> {}
+unsafe impl<T: Sync> Sync for UnrolledDeque<T> {}
+
+impl<T> UnrolledDeque<T> {
+    pub fn create() -> UnrolledDeque<T> {
+        UnrolledDeque {
+            first: std::ptr::null_mut(),
+            last: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_left(&mut self, x: T) {
+        if self.first.is_null() || !unsafe { (*self.first).has_room_left() } {
+            // new chunks on the left are filled from the right, leaving room for more pushes
+            let chunk = Chunk::create(CHUNK_CAPACITY);
+            unsafe { self.link_between(chunk, std::ptr::null_mut(), self.first) };
+        }
+
+        unsafe { (*self.first).push_left(x) };
+        self.len += 1;
+    }
+
+    pub fn push_right(&mut self, x: T) {
+        if self.last.is_null() || !unsafe { (*self.last).has_room_right() } {
+            let chunk = Chunk::create(0);
+            unsafe { self.link_between(chunk, self.last, std::ptr::null_mut()) };
+        }
+
+        unsafe { (*self.last).push_right(x) };
+        self.len += 1;
+    }
+
+    pub fn pop_left(&mut self) -> Option<T> {
+        if self.first.is_null() {
+            return None;
+        }
+
+        let chunk = self.first;
+        let x = unsafe { (*chunk).pop_left() };
+        self.len -= 1;
+        unsafe { self.release_if_empty(chunk) };
+
+        Some(x)
+    }
+
+    pub fn pop_right(&mut self) -> Option<T> {
+        if self.last.is_null() {
+            return None;
+        }
+
+        let chunk = self.last;
+        let x = unsafe { (*chunk).pop_right() };
+        self.len -= 1;
+        unsafe { self.release_if_empty(chunk) };
+
+        Some(x)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        unsafe { self.first.as_ref() }.map(|chunk| chunk.get(0))
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        unsafe { self.last.as_ref() }.map(|chunk| chunk.get(chunk.len - 1))
+    }
+
+    /// The value at an index, skipping whole chunks from whichever end is closest
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (chunk, offset) = self.locate(index)?;
+        Some(unsafe { (*chunk).get(offset) })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let (chunk, offset) = self.locate(index)?;
+        Some(unsafe { (*chunk).get_mut(offset) })
+    }
+
+    /// Finds the chunk holding an index and the offset of the index within it
+    fn locate(&self, index: usize) -> Option<(*mut Chunk<T>, usize)> {
+        if index >= self.len {
+            return None;
+        }
+
+        unsafe {
+            if index < self.len / 2 {
+                let mut chunk = self.first;
+                let mut offset = index;
+                while offset >= (*chunk).len {
+                    offset -= (*chunk).len;
+                    chunk = (*chunk).right_ptr;
+                }
+
+                Some((chunk, offset))
+            } else {
+                let mut chunk = self.last;
+                let mut from_right = self.len - 1 - index;
+                while from_right >= (*chunk).len {
+                    from_right -= (*chunk).len;
+                    chunk = (*chunk).left_ptr;
+                }
+
+                Some((chunk, (*chunk).len - 1 - from_right))
+            }
+        }
+    }
+
+    /// Links a chunk between two neighbouring chunks, either of which may be null at an end
+    ///
+    /// # Safety
+    ///
+    /// `left` and `right` must be neighbours in this deque and the chunk must not be linked.
+    unsafe fn link_between(
+        &mut self,
+        chunk: *mut Chunk<T>,
+        left: *mut Chunk<T>,
+        right: *mut Chunk<T>,
+    ) {
+        (*chunk).left_ptr = left;
+        (*chunk).right_ptr = right;
+
+        if left.is_null() {
+            self.first = chunk;
+        } else {
+            (*left).right_ptr = chunk;
+        }
+        if right.is_null() {
+            self.last = chunk;
+        } else {
+            (*right).left_ptr = chunk;
+        }
+    }
+
+    /// Unlinks and deallocates a chunk once its last value has been taken out
+    ///
+    /// # Safety
+    ///
+    /// The chunk must be part of this deque.
+    unsafe fn release_if_empty(&mut self, chunk: *mut Chunk<T>) -> bool {
+        if (*chunk).len > 0 {
+            return false;
+        }
+
+        let (left, right) = ((*chunk).left_ptr, (*chunk).right_ptr);
+        if left.is_null() {
+            self.first = right;
+        } else {
+            (*left).right_ptr = right;
+        }
+        if right.is_null() {
+            self.last = left;
+        } else {
+            (*right).left_ptr = left;
+        }
+        Chunk::destroy(chunk);
+
+        true
+    }
+
+    /// Inserts a value at `offset` of a chunk, splitting the chunk in two when it is full
+    ///
+    /// Returns where the value ended up.
+    ///
+    /// # Safety
+    ///
+    /// The chunk must be part of this deque and `offset` must be at most its length.
+    unsafe fn insert_in(
+        &mut self,
+        chunk: *mut Chunk<T>,
+        offset: usize,
+        x: T,
+    ) -> (*mut Chunk<T>, usize) {
+        self.len += 1;
+        if !(*chunk).is_full() {
+            (*chunk).insert(offset, x);
+            return (chunk, offset);
+        }
+
+        let half = CHUNK_CAPACITY / 2;
+        let right = Chunk::create(0);
+        (*chunk).move_tail_to(half, &mut *right);
+        self.link_between(right, chunk, (*chunk).right_ptr);
+
+        if offset <= half {
+            (*chunk).insert(offset, x);
+            (chunk, offset)
+        } else {
+            (*right).insert(offset - half, x);
+            (right, offset - half)
+        }
+    }
+
+    /// Removes the value at `offset` of a chunk, returning it and the position of the value
+    /// that followed it, which has a null chunk when the removed value was the last one
+    ///
+    /// # Safety
+    ///
+    /// The chunk must be part of this deque and `offset` must be less than its length.
+    unsafe fn remove_in(
+        &mut self,
+        chunk: *mut Chunk<T>,
+        offset: usize,
+    ) -> (T, *mut Chunk<T>, usize) {
+        let x = (*chunk).remove(offset);
+        self.len -= 1;
+
+        let right = (*chunk).right_ptr;
+        if self.release_if_empty(chunk) || offset == (*chunk).len {
+            (x, right, 0)
+        } else {
+            (x, chunk, offset)
+        }
+    }
+
+    /// Splits a chunk so that a chunk boundary falls right before `offset`
+    ///
+    /// Returns the chunks left and right of that boundary, either of which may be null at
+    /// an end. No chunk is left empty, so the values before `offset` stay where they were.
+    ///
+    /// # Safety
+    ///
+    /// The chunk must be part of this deque and `offset` must be at most its length.
+    unsafe fn boundary_before(
+        &mut self,
+        chunk: *mut Chunk<T>,
+        offset: usize,
+    ) -> (*mut Chunk<T>, *mut Chunk<T>) {
+        if offset == 0 {
+            return ((*chunk).left_ptr, chunk);
+        }
+        if offset == (*chunk).len {
+            return (chunk, (*chunk).right_ptr);
+        }
+
+        let right = Chunk::create(0);
+        (*chunk).move_tail_to(offset, &mut *right);
+        self.link_between(right, chunk, (*chunk).right_ptr);
+
+        (chunk, right)
+    }
+
+    /// Detaches the chunks from `first` up to and including `last` into a new deque
+    ///
+    /// # Safety
+    ///
+    /// `first` and `last` must both be part of this deque, with `first` not right of `last`,
+    /// and the chunks between them must hold `len` values.
+    unsafe fn detach(
+        &mut self,
+        first: *mut Chunk<T>,
+        last: *mut Chunk<T>,
+        len: usize,
+    ) -> UnrolledDeque<T> {
+        let (left, right) = ((*first).left_ptr, (*last).right_ptr);
+        if left.is_null() {
+            self.first = right;
+        } else {
+            (*left).right_ptr = right;
+        }
+        if right.is_null() {
+            self.last = left;
+        } else {
+            (*right).left_ptr = left;
+        }
+        self.len -= len;
+
+        (*first).left_ptr = std::ptr::null_mut();
+        (*last).right_ptr = std::ptr::null_mut();
+        UnrolledDeque { first, last, len }
+    }
+
+    /// Links all chunks of another deque between two neighbouring chunks
+    ///
+    /// # Safety
+    ///
+    /// `left` and `right` must be neighbours in this deque, either of which may be null at
+    /// an end.
+    unsafe fn splice_between(
+        &mut self,
+        mut other: UnrolledDeque<T>,
+        left: *mut Chunk<T>,
+        right: *mut Chunk<T>,
+    ) {
+        if other.is_empty() {
+            return;
+        }
+
+        (*other.first).left_ptr = left;
+        (*other.last).right_ptr = right;
+        if left.is_null() {
+            self.first = other.first;
+        } else {
+            (*left).right_ptr = other.first;
+        }
+        if right.is_null() {
+            self.last = other.last;
+        } else {
+            (*right).left_ptr = other.last;
+        }
+        self.len += other.len;
+
+        // the chunks now belong to this deque
+        other.first = std::ptr::null_mut();
+        other.last = std::ptr::null_mut();
+        other.len = 0;
+    }
+}
+
+impl<T> Drop for UnrolledDeque<T> {
+    fn drop(&mut self) {
+        let mut chunk = self.first;
+        while !chunk.is_null() {
+            unsafe {
+                let next = (*chunk).right_ptr;
+                (*chunk).drop_values();
+                Chunk::destroy(chunk);
+                chunk = next;
+            }
+        }
+    }
+}
+
+impl<T> Default for UnrolledDeque<T> {
+    fn default() -> Self {
+        UnrolledDeque::create()
+    }
+}
+
+impl<T: Clone> Clone for UnrolledDeque<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for UnrolledDeque<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for UnrolledDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for UnrolledDeque<T> {}
+
+impl<T> Extend<T> for UnrolledDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push_right(x);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for UnrolledDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = UnrolledDeque::create();
+        deque.extend(iter);
+
+        deque
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::unrolled::chunk::CHUNK_CAPACITY;
+        use crate::deque::unrolled::UnrolledDeque;
+        use std::rc::Rc;
+
+        #[test]
+        fn values_are_pushed_and_popped_at_both_ends() {
+            let mut data = UnrolledDeque::create();
+            data.push_right(2);
+            data.push_left(1);
+            data.push_right(3);
+
+            assert_eq!(data.len(), 3);
+            assert_eq!(data.first(), Some(&1));
+            assert_eq!(data.last(), Some(&3));
+            assert_eq!(data.pop_left(), Some(1));
+            assert_eq!(data.pop_right(), Some(3));
+            assert_eq!(data.pop_right(), Some(2));
+            assert_eq!(data.pop_left(), None);
+            assert!(data.is_empty());
+        }
+
+        #[test]
+        fn values_span_several_chunks() {
+            let mut data: UnrolledDeque<usize> = (0..CHUNK_CAPACITY * 3).collect();
+            for x in 1..=CHUNK_CAPACITY {
+                data.push_left(CHUNK_CAPACITY * 3 + x);
+            }
+
+            assert_eq!(data.len(), CHUNK_CAPACITY * 4);
+            assert_eq!(data.get(0), Some(&(CHUNK_CAPACITY * 4)));
+            assert_eq!(data.get(CHUNK_CAPACITY), Some(&0));
+            assert_eq!(
+                data.get(CHUNK_CAPACITY * 4 - 1),
+                Some(&(CHUNK_CAPACITY * 3 - 1))
+            );
+            assert_eq!(data.get(CHUNK_CAPACITY * 4), None);
+        }
+
+        #[test]
+        fn values_can_be_changed_by_index() {
+            let mut data: UnrolledDeque<usize> = (0..40).collect();
+            *data.get_mut(30).unwrap() = 0;
+
+            assert_eq!(data.get(30), Some(&0));
+            assert_eq!(data.get_mut(40), None);
+        }
+
+        #[test]
+        fn values_are_dropped_with_the_deque() {
+            let counter = Rc::new(());
+            {
+                let mut data = UnrolledDeque::create();
+                for _ in 0..40 {
+                    data.push_left(Rc::clone(&counter));
+                }
+                drop(data.pop_right());
+            }
+
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+
+        #[test]
+        fn cloned_deques_are_equal() {
+            let data: UnrolledDeque<String> = ["a", "b"].iter().map(|x| x.to_string()).collect();
+
+            assert_eq!(data.clone(), data);
+            assert_eq!(format!("{:?}", data), "[\"a\", \"b\"]");
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::unrolled::UnrolledDeque;
+        use proptest::prelude::*;
+        use std::collections::VecDeque;
+
+        #[derive(Debug, Clone)]
+        enum Operation {
+            PushLeft(u8),
+            PushRight(u8),
+            PopLeft,
+            PopRight,
+        }
+
+        fn operation() -> impl Strategy<Value = Operation> {
+            prop_oneof![
+                any::<u8>().prop_map(Operation::PushLeft),
+                any::<u8>().prop_map(Operation::PushRight),
+                Just(Operation::PopLeft),
+                Just(Operation::PopRight),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn operations_match_a_vec_deque(operations in prop::collection::vec(operation(), 0..200)) {
+                let mut data = UnrolledDeque::create();
+                let mut expected = VecDeque::new();
+
+                for operation in operations {
+                    match operation {
+                        Operation::PushLeft(x) => { data.push_left(x); expected.push_front(x); }
+                        Operation::PushRight(x) => { data.push_right(x); expected.push_back(x); }
+                        Operation::PopLeft => prop_assert_eq!(data.pop_left(), expected.pop_front()),
+                        Operation::PopRight => prop_assert_eq!(data.pop_right(), expected.pop_back()),
+                    }
+                }
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(data.iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>());
+                prop_assert_eq!(data.iter().rev().collect::<Vec<_>>(), expected.iter().rev().collect::<Vec<_>>());
+                for (index, value) in expected.iter().enumerate() {
+                    prop_assert_eq!(data.get(index), Some(value));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::mem::MaybeUninit;
+use std::ptr;
+
+/// The number of values a single chunk can hold
+pub const CHUNK_CAPACITY: usize = 16;
+
+/// A node of an [UnrolledDeque](crate::deque::unrolled::UnrolledDeque), holding its values
+/// contiguously in `values[start..start + len]`.
+///
+/// Values are addressed by their offset from `start`, so shifting the values of a chunk
+/// does not change the offsets of the values that stay in place relative to each other.
+pub(in crate::deque::unrolled) struct Chunk<T> {
+    pub(in crate::deque::unrolled) left_ptr: *mut Chunk<T>,
+    pub(in crate::deque::unrolled) right_ptr: *mut Chunk<T>,
+    pub(in crate::deque::unrolled) start: usize,
+    pub(in crate::deque::unrolled) len: usize,
+    values: [MaybeUninit<T>; CHUNK_CAPACITY],
+}
+
+impl<T> Chunk<T> {
+    /// Allocates an empty chunk whose values will start at `start`
+    pub(in crate::deque::unrolled) fn create(start: usize) -> *mut Chunk<T> {
+        let layout = std::alloc::Layout::new::<Chunk<T>>();
+        let ptr = unsafe { std::alloc::alloc(layout) as *mut Chunk<T> };
+        if ptr.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
+
+        // the values stay uninitialised, so only the header is written
+        unsafe {
+            ptr::addr_of_mut!((*ptr).left_ptr).write(ptr::null_mut());
+            ptr::addr_of_mut!((*ptr).right_ptr).write(ptr::null_mut());
+            ptr::addr_of_mut!((*ptr).start).write(start);
+            ptr::addr_of_mut!((*ptr).len).write(0);
+        }
+
+        ptr
+    }
+
+    /// Deallocates a chunk without dropping its values
+    ///
+    /// # Safety
+    ///
+    /// The pointer must come from [Chunk::create] and must not be used afterwards.
+    pub(in crate::deque::unrolled) unsafe fn destroy(ptr: *mut Chunk<T>) {
+        std::alloc::dealloc(ptr as *mut u8, std::alloc::Layout::new::<Chunk<T>>());
+    }
+
+    pub(in crate::deque::unrolled) fn is_full(&self) -> bool {
+        self.len == CHUNK_CAPACITY
+    }
+
+    /// Whether a value can be added to the right without shifting
+    pub(in crate::deque::unrolled) fn has_room_right(&self) -> bool {
+        self.start + self.len < CHUNK_CAPACITY
+    }
+
+    /// Whether a value can be added to the left without shifting
+    pub(in crate::deque::unrolled) fn has_room_left(&self) -> bool {
+        self.start > 0
+    }
+
+    /// A pointer to the slot at `offset`, which may be one past the last slot
+    fn slot(&mut self, offset: usize) -> *mut T {
+        debug_assert!(self.start + offset <= CHUNK_CAPACITY);
+        unsafe { (self.values.as_mut_ptr() as *mut T).add(self.start + offset) }
+    }
+
+    pub(in crate::deque::unrolled) fn get(&self, offset: usize) -> &T {
+        debug_assert!(offset < self.len);
+        unsafe { self.values[self.start + offset].assume_init_ref() }
+    }
+
+    pub(in crate::deque::unrolled) fn get_mut(&mut self, offset: usize) -> &mut T {
+        debug_assert!(offset < self.len);
+        unsafe { self.values[self.start + offset].assume_init_mut() }
+    }
+
+    pub(in crate::deque::unrolled) fn push_left(&mut self, x: T) {
+        debug_assert!(self.has_room_left());
+        self.start -= 1;
+        self.len += 1;
+        unsafe { self.slot(0).write(x) };
+    }
+
+    pub(in crate::deque::unrolled) fn push_right(&mut self, x: T) {
+        debug_assert!(self.has_room_right());
+        self.len += 1;
+        let offset = self.len - 1;
+        unsafe { self.slot(offset).write(x) };
+    }
+
+    pub(in crate::deque::unrolled) fn pop_left(&mut self) -> T {
+        debug_assert!(self.len > 0);
+        let x = unsafe { self.slot(0).read() };
+        self.start += 1;
+        self.len -= 1;
+
+        x
+    }
+
+    pub(in crate::deque::unrolled) fn pop_right(&mut self) -> T {
+        debug_assert!(self.len > 0);
+        self.len -= 1;
+        let offset = self.len;
+
+        unsafe { self.slot(offset).read() }
+    }
+
+    /// Inserts a value at `offset`, shifting the values on whichever side has room
+    pub(in crate::deque::unrolled) fn insert(&mut self, offset: usize, x: T) {
+        debug_assert!(!self.is_full() && offset <= self.len);
+        unsafe {
+            if self.has_room_right() {
+                let at = self.slot(offset);
+                ptr::copy(at, at.add(1), self.len - offset);
+            } else {
+                let first = self.slot(0);
+                ptr::copy(first, first.sub(1), offset);
+                self.start -= 1;
+            }
+            self.len += 1;
+            self.slot(offset).write(x);
+        }
+    }
+
+    /// Removes the value at `offset`, shifting the values on whichever side is shorter
+    pub(in crate::deque::unrolled) fn remove(&mut self, offset: usize) -> T {
+        debug_assert!(offset < self.len);
+        unsafe {
+            let x = self.slot(offset).read();
+            if offset < self.len / 2 {
+                let first = self.slot(0);
+                ptr::copy(first, first.add(1), offset);
+                self.start += 1;
+            } else {
+                let at = self.slot(offset);
+                ptr::copy(at.add(1), at, self.len - offset - 1);
+            }
+            self.len -= 1;
+
+            x
+        }
+    }
+
+    /// Moves the values from `offset` onwards to the start of an empty chunk
+    pub(in crate::deque::unrolled) fn move_tail_to(&mut self, offset: usize, other: &mut Chunk<T>) {
+        debug_assert!(other.len == 0 && offset <= self.len);
+        let moved = self.len - offset;
+        unsafe { ptr::copy_nonoverlapping(self.slot(offset), other.values[0].as_mut_ptr(), moved) };
+
+        other.start = 0;
+        other.len = moved;
+        self.len = offset;
+    }
+
+    /// Drops the values that are still in the chunk
+    pub(in crate::deque::unrolled) fn drop_values(&mut self) {
+        for offset in 0..self.len {
+            unsafe { ptr::drop_in_place(self.slot(offset)) };
+        }
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque::unrolled::chunk::{Chunk, CHUNK_CAPACITY};
+
+    fn values(chunk: &Chunk<usize>) -> Vec<usize> {
+        (0..chunk.len).map(|offset| *chunk.get(offset)).collect()
+    }
+
+    #[test]
+    fn inserting_shifts_towards_the_free_side() {
+        let ptr = Chunk::create(CHUNK_CAPACITY - 3);
+        let chunk = unsafe { &mut *ptr };
+        chunk.push_right(1);
+        chunk.push_right(3);
+        chunk.push_right(4);
+
+        // the right side is full, so the first value moves left
+        chunk.insert(1, 2);
+        assert_eq!(values(chunk), vec![1, 2, 3, 4]);
+        assert_eq!(chunk.start, CHUNK_CAPACITY - 4);
+
+        assert_eq!(chunk.remove(1), 2);
+        assert_eq!(chunk.remove(2), 4);
+        assert_eq!(values(chunk), vec![1, 3]);
+
+        chunk.drop_values();
+        unsafe { Chunk::destroy(ptr) };
+    }
+
+    #[test]
+    fn tails_move_to_another_chunk() {
+        let (a, b) = (Chunk::create(0), Chunk::create(0));
+        let (left, right) = unsafe { (&mut *a, &mut *b) };
+        for x in 0..CHUNK_CAPACITY {
+            left.push_right(x);
+        }
+        left.move_tail_to(10, right);
+
+        assert_eq!(values(left), (0..10).collect::<Vec<_>>());
+        assert_eq!(values(right), (10..CHUNK_CAPACITY).collect::<Vec<_>>());
+
+        unsafe {
+            Chunk::destroy(a);
+            Chunk::destroy(b);
+        }
+    }
+}
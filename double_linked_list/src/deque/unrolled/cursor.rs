@@ -0,0 +1,615 @@
+use crate::deque::unrolled::chunk::Chunk;
+use crate::deque::unrolled::UnrolledDeque;
+
+/// A read-only position in an [UnrolledDeque] that can move in both directions.
+///
+/// Like a [Cursor](crate::deque::cursor::Cursor) of a `Deque`, it can also point at a
+/// "ghost" position between the last and the first value.
+pub struct Cursor<'a, T> {
+    index: usize,
+    chunk: *mut Chunk<T>,
+    offset: usize,
+    deque: &'a UnrolledDeque<T>,
+}
+
+/// A position in an [UnrolledDeque] that can insert and remove values around it.
+///
+/// Editing moves values within their chunk, or splits a full chunk, so it takes
+/// O([CHUNK_CAPACITY](crate::deque::unrolled::CHUNK_CAPACITY)) rather than O(1).
+pub struct CursorMut<'a, T> {
+    index: usize,
+    chunk: *mut Chunk<T>,
+    offset: usize,
+    deque: &'a mut UnrolledDeque<T>,
+}
+
+impl<T> UnrolledDeque<T> {
+    /// A cursor pointing at the first value, or at the ghost position when the deque is empty
+    pub fn cursor_first(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: 0,
+            chunk: self.first,
+            offset: 0,
+            deque: self,
+        }
+    }
+
+    /// A cursor pointing at the last value, or at the ghost position when the deque is empty
+    pub fn cursor_last(&self) -> Cursor<'_, T> {
+        let (chunk, offset) = self.position_before(std::ptr::null_mut(), 0);
+        Cursor {
+            index: self.len.saturating_sub(1),
+            chunk,
+            offset,
+            deque: self,
+        }
+    }
+
+    pub fn cursor_first_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            chunk: self.first,
+            offset: 0,
+            deque: self,
+        }
+    }
+
+    pub fn cursor_last_mut(&mut self) -> CursorMut<'_, T> {
+        let (chunk, offset) = self.position_before(std::ptr::null_mut(), 0);
+        CursorMut {
+            index: self.len.saturating_sub(1),
+            chunk,
+            offset,
+            deque: self,
+        }
+    }
+
+    /// The position right of a position, where a null chunk is the ghost position
+    fn position_after(&self, chunk: *mut Chunk<T>, offset: usize) -> (*mut Chunk<T>, usize) {
+        if chunk.is_null() {
+            (self.first, 0)
+        } else if offset + 1 < unsafe { (*chunk).len } {
+            (chunk, offset + 1)
+        } else {
+            (unsafe { (*chunk).right_ptr }, 0)
+        }
+    }
+
+    /// The position left of a position, where a null chunk is the ghost position
+    fn position_before(&self, chunk: *mut Chunk<T>, offset: usize) -> (*mut Chunk<T>, usize) {
+        let left = if chunk.is_null() {
+            self.last
+        } else if offset > 0 {
+            return (chunk, offset - 1);
+        } else {
+            unsafe { (*chunk).left_ptr }
+        };
+
+        match unsafe { left.as_ref() } {
+            Some(left_chunk) => (left, left_chunk.len - 1),
+            None => (std::ptr::null_mut(), 0),
+        }
+    }
+}
+
+// moves a cursor one position, shared between both kinds of cursor
+macro_rules! move_cursor {
+    ($cursor: expr, right) => {
+        if $cursor.chunk.is_null() {
+            // moving right from the ghost wraps around to the first value
+            $cursor.index = 0;
+        } else {
+            $cursor.index += 1;
+        }
+        ($cursor.chunk, $cursor.offset) =
+            $cursor.deque.position_after($cursor.chunk, $cursor.offset);
+    };
+    ($cursor: expr, left) => {
+        if $cursor.chunk.is_null() {
+            // moving left from the ghost wraps around to the last value
+            $cursor.index = $cursor.deque.len.saturating_sub(1);
+        } else {
+            $cursor.index = match $cursor.index.checked_sub(1) {
+                Some(index) => index,
+                // the ghost position is indexed as if it came after the last value
+                None => $cursor.deque.len,
+            };
+        }
+        ($cursor.chunk, $cursor.offset) =
+            $cursor.deque.position_before($cursor.chunk, $cursor.offset);
+    };
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// The index of the current value, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        if self.chunk.is_null() {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        move_cursor!(self, right);
+    }
+
+    pub fn move_prev(&mut self) {
+        move_cursor!(self, left);
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.chunk.as_ref() }.map(|chunk| chunk.get(self.offset))
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let (chunk, offset) = self.deque.position_after(self.chunk, self.offset);
+        unsafe { chunk.as_ref() }.map(|chunk| chunk.get(offset))
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let (chunk, offset) = self.deque.position_before(self.chunk, self.offset);
+        unsafe { chunk.as_ref() }.map(|chunk| chunk.get(offset))
+    }
+}
+
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        Cursor {
+            index: self.index,
+            chunk: self.chunk,
+            offset: self.offset,
+            deque: self.deque,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// The index of the current value, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        if self.chunk.is_null() {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        move_cursor!(self, right);
+    }
+
+    pub fn move_prev(&mut self) {
+        move_cursor!(self, left);
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.chunk.as_mut() }.map(|chunk| chunk.get_mut(self.offset))
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let (chunk, offset) = self.deque.position_after(self.chunk, self.offset);
+        unsafe { chunk.as_mut() }.map(|chunk| chunk.get_mut(offset))
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let (chunk, offset) = self.deque.position_before(self.chunk, self.offset);
+        unsafe { chunk.as_mut() }.map(|chunk| chunk.get_mut(offset))
+    }
+
+    /// A read-only cursor at the same position, borrowing this cursor
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: self.index,
+            chunk: self.chunk,
+            offset: self.offset,
+            deque: self.deque,
+        }
+    }
+
+    /// Inserts a value after the current value, or at the start when at the ghost position
+    pub fn insert_after(&mut self, x: T) {
+        if self.chunk.is_null() {
+            self.deque.push_left(x);
+            self.index += 1;
+            return;
+        }
+
+        // the current value may have moved, but it is still right before the inserted one
+        let (chunk, offset) = unsafe { self.deque.insert_in(self.chunk, self.offset + 1, x) };
+        (self.chunk, self.offset) = self.deque.position_before(chunk, offset);
+    }
+
+    /// Inserts a value before the current value, or at the end when at the ghost position
+    pub fn insert_before(&mut self, x: T) {
+        self.index += 1;
+        if self.chunk.is_null() {
+            self.deque.push_right(x);
+            return;
+        }
+
+        let (chunk, offset) = unsafe { self.deque.insert_in(self.chunk, self.offset, x) };
+        (self.chunk, self.offset) = self.deque.position_after(chunk, offset);
+    }
+
+    /// Removes the current value and moves the cursor to the value right of it
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.chunk.is_null() {
+            return None;
+        }
+
+        let (x, chunk, offset) = unsafe { self.deque.remove_in(self.chunk, self.offset) };
+        (self.chunk, self.offset) = (chunk, offset);
+
+        Some(x)
+    }
+
+    /// Moves every value right of the cursor into a new deque
+    ///
+    /// Only the chunk of the current value is split, the chunks right of it are relinked,
+    /// so this takes O([CHUNK_CAPACITY](crate::deque::unrolled::CHUNK_CAPACITY)). At the
+    /// ghost position the whole deque is moved.
+    pub fn split_after(&mut self) -> UnrolledDeque<T> {
+        if self.chunk.is_null() {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        }
+
+        let len = self.deque.len - self.index - 1;
+        unsafe {
+            let (_, right) = self.deque.boundary_before(self.chunk, self.offset + 1);
+            if right.is_null() {
+                return UnrolledDeque::create();
+            }
+            let last = self.deque.last;
+            self.deque.detach(right, last, len)
+        }
+    }
+
+    /// Moves every value left of the cursor into a new deque
+    ///
+    /// See [CursorMut::split_after] for what this costs.
+    pub fn split_before(&mut self) -> UnrolledDeque<T> {
+        if self.chunk.is_null() {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        }
+
+        let len = std::mem::replace(&mut self.index, 0);
+        unsafe {
+            // the current value starts a chunk after the split
+            let (left, right) = self.deque.boundary_before(self.chunk, self.offset);
+            (self.chunk, self.offset) = (right, 0);
+            if left.is_null() {
+                return UnrolledDeque::create();
+            }
+            let first = self.deque.first;
+            self.deque.detach(first, left, len)
+        }
+    }
+
+    /// Inserts all values of another deque after the current value
+    ///
+    /// The chunks of `other` are relinked rather than copied, so this takes
+    /// O([CHUNK_CAPACITY](crate::deque::unrolled::CHUNK_CAPACITY)) to split the chunk of the
+    /// current value. At the ghost position the values are inserted at the start.
+    pub fn splice_after(&mut self, other: UnrolledDeque<T>) {
+        let len = other.len;
+        if self.chunk.is_null() {
+            let first = self.deque.first;
+            unsafe {
+                self.deque
+                    .splice_between(other, std::ptr::null_mut(), first)
+            };
+            self.index += len;
+            return;
+        }
+
+        unsafe {
+            let (left, right) = self.deque.boundary_before(self.chunk, self.offset + 1);
+            self.deque.splice_between(other, left, right);
+        }
+    }
+
+    /// Inserts all values of another deque before the current value
+    ///
+    /// See [CursorMut::splice_after] for what this costs. At the ghost position the values
+    /// are inserted at the end.
+    pub fn splice_before(&mut self, other: UnrolledDeque<T>) {
+        self.index += other.len;
+        if self.chunk.is_null() {
+            let last = self.deque.last;
+            unsafe { self.deque.splice_between(other, last, std::ptr::null_mut()) };
+            return;
+        }
+
+        unsafe {
+            let (left, right) = self.deque.boundary_before(self.chunk, self.offset);
+            (self.chunk, self.offset) = (right, 0);
+            self.deque.splice_between(other, left, right);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::deque::unrolled::UnrolledDeque;
+        use crate::test_helpers::{values, values_reversed};
+
+        #[test]
+        fn a_cursor_walks_the_deque_and_wraps_around_the_ghost() {
+            let data: UnrolledDeque<usize> = (0..40).collect();
+            let mut cursor = data.cursor_first();
+            for x in 0..40 {
+                assert_eq!(cursor.index(), Some(x));
+                assert_eq!(cursor.current(), Some(&x));
+                cursor.move_next();
+            }
+
+            assert_eq!(cursor.index(), None);
+            assert_eq!(cursor.peek_next(), Some(&0));
+            assert_eq!(cursor.peek_prev(), Some(&39));
+
+            cursor.move_prev();
+            assert_eq!(cursor.index(), Some(39));
+            assert_eq!(cursor.peek_prev(), Some(&38));
+        }
+
+        #[test]
+        fn a_cursor_on_an_empty_deque_is_at_the_ghost_position() {
+            let data: UnrolledDeque<usize> = UnrolledDeque::create();
+            let cursor = data.cursor_last();
+
+            assert_eq!(cursor.index(), None);
+            assert_eq!(cursor.current(), None);
+            assert_eq!(cursor.peek_next(), None);
+        }
+
+        #[test]
+        fn values_can_be_inserted_into_a_full_chunk() {
+            let mut data: UnrolledDeque<usize> = (0..17).filter(|x| *x != 5).collect();
+            data.push_left(100);
+            let mut cursor = data.cursor_first_mut();
+            for _ in 0..5 {
+                cursor.move_next();
+            }
+            assert_eq!(cursor.current(), Some(&mut 4));
+
+            cursor.insert_after(5);
+            cursor.insert_before(3);
+
+            assert_eq!(cursor.current(), Some(&mut 4));
+            assert_eq!(cursor.index(), Some(6));
+            assert_eq!(cursor.peek_next(), Some(&mut 5));
+            assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        }
+
+        #[test]
+        fn inserting_at_the_ghost_position_inserts_at_the_ends() {
+            let mut data: UnrolledDeque<usize> = (1..3).collect();
+            let mut cursor = data.cursor_first_mut();
+            cursor.move_prev();
+            cursor.insert_after(0);
+            cursor.insert_before(3);
+
+            assert_eq!(cursor.index(), None);
+            cursor.move_prev();
+            assert_eq!(cursor.index(), Some(3));
+            assert_eq!(values(&data), vec![0, 1, 2, 3]);
+        }
+
+        #[test]
+        fn removing_moves_the_cursor_to_the_next_value() {
+            let mut data: UnrolledDeque<usize> = (0..20).collect();
+            let mut cursor = data.cursor_first_mut();
+            for x in 0..20 {
+                if x % 2 == 0 {
+                    assert_eq!(cursor.remove_current(), Some(x));
+                } else {
+                    cursor.move_next();
+                }
+            }
+
+            assert_eq!(cursor.index(), None);
+            assert_eq!(cursor.remove_current(), None);
+            assert_eq!(values(&data), (1..20).step_by(2).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn splitting_around_the_cursor_splits_its_chunk() {
+            let mut data: UnrolledDeque<usize> = (0..40).collect();
+            let mut cursor = data.cursor_first_mut();
+            for _ in 0..20 {
+                cursor.move_next();
+            }
+            let tail = cursor.split_after();
+            let head = cursor.split_before();
+
+            assert_eq!(cursor.index(), Some(0));
+            assert_eq!(cursor.current(), Some(&mut 20));
+            assert_eq!(cursor.peek_next(), None);
+            assert_eq!(values(&head), (0..20).collect::<Vec<_>>());
+            assert_eq!(values(&data), vec![20]);
+            assert_eq!(values_reversed(&tail), (21..40).collect::<Vec<_>>());
+            assert_eq!((head.len(), data.len(), tail.len()), (20, 1, 19));
+        }
+
+        #[test]
+        fn splitting_at_the_ghost_position_moves_everything() {
+            let mut data: UnrolledDeque<usize> = (1..3).collect();
+            let mut cursor = data.cursor_first_mut();
+            cursor.move_prev();
+            let all = cursor.split_before();
+
+            assert_eq!(cursor.index(), None);
+            assert_eq!(values(&all), vec![1, 2]);
+            assert!(data.is_empty());
+            assert!(data.cursor_first_mut().split_after().is_empty());
+        }
+
+        #[test]
+        fn splicing_around_the_cursor() {
+            let mut data: UnrolledDeque<usize> = [1, 20].into_iter().collect();
+            let mut cursor = data.cursor_first_mut();
+            cursor.splice_after((2..20).collect());
+            assert_eq!(cursor.index(), Some(0));
+            assert_eq!(cursor.peek_next(), Some(&mut 2));
+
+            cursor.move_prev();
+            cursor.splice_after([0].into_iter().collect());
+            cursor.splice_before([21, 22].into_iter().collect());
+            cursor.splice_before(UnrolledDeque::create());
+
+            assert_eq!(cursor.index(), None);
+            cursor.move_prev();
+            assert_eq!(
+                (cursor.index(), cursor.current()),
+                (Some(22), Some(&mut 22))
+            );
+            assert_eq!(values(&data), (0..23).collect::<Vec<_>>());
+            assert_eq!(values_reversed(&data), (0..23).collect::<Vec<_>>());
+        }
+    }
+
+    mod property_tests {
+        use crate::deque::unrolled::UnrolledDeque;
+        use crate::test_helpers::{values, values_reversed};
+        use proptest::prelude::*;
+
+        #[derive(Debug, Clone)]
+        enum Edit {
+            Next,
+            Prev,
+            InsertAfter(u8),
+            InsertBefore(u8),
+            Remove,
+        }
+
+        fn edit() -> impl Strategy<Value = Edit> {
+            prop_oneof![
+                Just(Edit::Next),
+                Just(Edit::Prev),
+                any::<u8>().prop_map(Edit::InsertAfter),
+                any::<u8>().prop_map(Edit::InsertBefore),
+                Just(Edit::Remove),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn editing_matches_a_vec(initial: Vec<u8>, edits in prop::collection::vec(edit(), 0..200)) {
+                let mut data: UnrolledDeque<u8> = initial.iter().copied().collect();
+                let mut expected = initial;
+                // the ghost position is modelled as index `expected.len()`
+                let mut index = 0;
+                let mut cursor = data.cursor_first_mut();
+
+                for edit in edits {
+                    let at_ghost = index == expected.len();
+                    match edit {
+                        Edit::Next => index = if at_ghost { 0 } else { index + 1 },
+                        Edit::Prev => index = if index == 0 { expected.len() } else { index - 1 },
+                        Edit::InsertAfter(x) => {
+                            if at_ghost { expected.insert(0, x); } else { expected.insert(index + 1, x); }
+                            cursor.insert_after(x);
+                            if at_ghost { index += 1; }
+                        }
+                        Edit::InsertBefore(x) => {
+                            expected.insert(index, x);
+                            index += 1;
+                            cursor.insert_before(x);
+                        }
+                        Edit::Remove => {
+                            let removed = if at_ghost { None } else { Some(expected.remove(index)) };
+                            prop_assert_eq!(cursor.remove_current(), removed);
+                        }
+                    }
+                    match edit {
+                        Edit::Next => cursor.move_next(),
+                        Edit::Prev => cursor.move_prev(),
+                        _ => {}
+                    }
+
+                    let at_ghost = index == expected.len();
+                    prop_assert_eq!(cursor.index(), if at_ghost { None } else { Some(index) });
+                    prop_assert_eq!(cursor.as_cursor().current(), expected.get(index));
+                }
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(data.iter().copied().collect::<Vec<_>>(), expected.clone());
+                prop_assert_eq!(data.iter().rev().copied().collect::<Vec<_>>(), expected.into_iter().rev().collect::<Vec<_>>());
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn splitting_around_the_cursor_matches_a_vec(original: Vec<u8>, steps in 0..100usize) {
+                let mut data: UnrolledDeque<u8> = original.iter().copied().collect();
+                let mut cursor = data.cursor_first_mut();
+                for _ in 0..steps {
+                    cursor.move_next();
+                }
+
+                match cursor.index() {
+                    Some(at) => {
+                        let tail = cursor.split_after();
+                        let head = cursor.split_before();
+
+                        prop_assert_eq!(cursor.as_cursor().current(), original.get(at));
+                        prop_assert_eq!(values(&head), &original[..at]);
+                        prop_assert_eq!(values(&data), &original[at..at + 1]);
+                        prop_assert_eq!(values_reversed(&tail), &original[at + 1..]);
+                        prop_assert_eq!(head.len() + data.len() + tail.len(), original.len());
+                    }
+                    None => {
+                        // at the ghost position everything is moved
+                        let all = cursor.split_after();
+
+                        prop_assert_eq!(values_reversed(&all), original);
+                        prop_assert!(data.is_empty());
+                    }
+                }
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn splicing_around_the_cursor_matches_a_vec(
+                original: Vec<u8>,
+                before: Vec<u8>,
+                after: Vec<u8>,
+                steps in 0..100usize,
+            ) {
+                let mut data: UnrolledDeque<u8> = original.iter().copied().collect();
+                let mut cursor = data.cursor_first_mut();
+                for _ in 0..steps {
+                    cursor.move_next();
+                }
+                let at = cursor.index();
+                cursor.splice_before(before.iter().copied().collect());
+                cursor.splice_after(after.iter().copied().collect());
+
+                let expected: Vec<u8> = match at {
+                    Some(at) => {
+                        prop_assert_eq!(cursor.index(), Some(at + before.len()));
+                        prop_assert_eq!(cursor.as_cursor().current(), original.get(at));
+                        [&original[..at], &before, &original[at..at + 1], &after, &original[at + 1..]].concat()
+                    }
+                    None => {
+                        prop_assert_eq!(cursor.index(), None);
+                        [&after[..], &original, &before].concat()
+                    }
+                };
+
+                // pushing after the splices checks that the chunks are still linked up
+                data.push_left(0);
+                data.push_right(0);
+                let expected = [&[0][..], &expected, &[0]].concat();
+
+                prop_assert_eq!(data.len(), expected.len());
+                prop_assert_eq!(values(&data), expected.clone());
+                prop_assert_eq!(values_reversed(&data), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::deque::unrolled::chunk::Chunk;
+use crate::deque::unrolled::UnrolledDeque;
+use std::marker::PhantomData;
+
+/// Iterates over the values of an [UnrolledDeque], from left to right or from right to left
+pub struct Iter<'a, T> {
+    // the next value from the left is at `offset` of `front`
+    front: *mut Chunk<T>,
+    offset: usize,
+    // the next value from the right is just before `back_offset` of `back`
+    back: *mut Chunk<T>,
+    back_offset: usize,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+/// Iterates over mutable references to the values of an [UnrolledDeque]
+pub struct IterMut<'a, T> {
+    front: *mut Chunk<T>,
+    offset: usize,
+    back: *mut Chunk<T>,
+    back_offset: usize,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+/// Moves the values out of an [UnrolledDeque]
+pub struct IntoIter<T> {
+    deque: UnrolledDeque<T>,
+}
+
+impl<T> UnrolledDeque<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.first,
+            offset: 0,
+            back: self.last,
+            back_offset: unsafe { self.last.as_ref() }.map_or(0, |chunk| chunk.len),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.first,
+            offset: 0,
+            back: self.last,
+            back_offset: unsafe { self.last.as_ref() }.map_or(0, |chunk| chunk.len),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+// steps over the chunks from either end, shared between both kinds of iterator
+macro_rules! walk {
+    ($iter: expr, front) => {{
+        if $iter.len == 0 {
+            return None;
+        }
+        unsafe {
+            if $iter.offset == (*$iter.front).len {
+                $iter.front = (*$iter.front).right_ptr;
+                $iter.offset = 0;
+            }
+            $iter.len -= 1;
+            $iter.offset += 1;
+
+            ($iter.front, $iter.offset - 1)
+        }
+    }};
+    ($iter: expr, back) => {{
+        if $iter.len == 0 {
+            return None;
+        }
+        unsafe {
+            if $iter.back_offset == 0 {
+                $iter.back = (*$iter.back).left_ptr;
+                $iter.back_offset = (*$iter.back).len;
+            }
+            $iter.len -= 1;
+            $iter.back_offset -= 1;
+
+            ($iter.back, $iter.back_offset)
+        }
+    }};
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (chunk, offset) = walk!(self, front);
+        Some(unsafe { (*chunk).get(offset) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (chunk, offset) = walk!(self, back);
+        Some(unsafe { (*chunk).get(offset) })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            front: self.front,
+            offset: self.offset,
+            back: self.back,
+            back_offset: self.back_offset,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (chunk, offset) = walk!(self, front);
+        Some(unsafe { (*chunk).get_mut(offset) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (chunk, offset) = walk!(self, back);
+        Some(unsafe { (*chunk).get_mut(offset) })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_left()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_right()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<'a, T> IntoIterator for &'a UnrolledDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut UnrolledDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for UnrolledDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque::unrolled::UnrolledDeque;
+
+    #[test]
+    fn iterating_from_both_ends_meets_in_the_middle() {
+        let data: UnrolledDeque<usize> = (0..50).collect();
+        let mut iter = data.iter();
+
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&49));
+        assert_eq!(iter.len(), 48);
+        assert_eq!(iter.copied().sum::<usize>(), (1..49).sum());
+    }
+
+    #[test]
+    fn values_can_be_changed_while_iterating() {
+        let mut data: UnrolledDeque<usize> = (0..40).collect();
+        for value in &mut data {
+            *value *= 2;
+        }
+
+        assert_eq!(data.iter().next_back(), Some(&78));
+        assert_eq!(data.into_iter().take(3).collect::<Vec<_>>(), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn iterating_an_empty_deque() {
+        let mut data: UnrolledDeque<usize> = UnrolledDeque::create();
+
+        assert_eq!(data.iter().next(), None);
+        assert_eq!(data.iter_mut().next_back(), None);
+        assert_eq!(data.into_iter().next(), None);
+    }
+}
@@ -0,0 +1,365 @@
+//! A Chase-Lev work-stealing deque, following "Correct and Efficient Work-Stealing for Weak
+//! Memory Models" (Lê, Pop, Cohen and Zappa Nardelli, 2013).
+//!
+//! The owning [Worker] pushes and pops at the bottom, while any number of [Stealer]s take
+//! from the top. Values live in a circular buffer that doubles when full. A buffer that is
+//! replaced is retired rather than freed, since a stealer may still be reading from it, and
+//! retired buffers are freed by the worker as soon as no stealer is in the middle of a steal.
+
+use std::alloc::Layout;
+use std::cell::{Cell, UnsafeCell};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicIsize, AtomicPtr, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const MIN_CAPACITY: usize = 16;
+
+/// The outcome of [Stealer::steal]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Steal<T> {
+    /// There was nothing to steal
+    Empty,
+    Success(T),
+    /// Another thread took the value first, so stealing again may succeed
+    Retry,
+}
+
+/// The owning end of a work-stealing deque, pushing and popping at the bottom
+pub struct Worker<T> {
+    inner: Arc<Inner<T>>,
+    // only one thread may push and pop, so the worker can be sent but not shared
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+/// A thief of a work-stealing deque, taking values from the top
+pub struct Stealer<T> {
+    inner: Arc<Inner<T>>,
+}
+
+struct Inner<T> {
+    top: AtomicIsize,
+    bottom: AtomicIsize,
+    buffer: AtomicPtr<Buffer<T>>,
+    // the number of stealers that may be holding a pointer to a buffer
+    active_stealers: AtomicUsize,
+    // replaced buffers that may still be read, only touched by the worker and by drop
+    retired: UnsafeCell<Vec<*mut Buffer<T>>>,
+}
+
+/// A circular array of slots whose capacity is a power of two
+struct Buffer<T> {
+    slots: *mut MaybeUninit<T>,
+    capacity: usize,
+}
+
+impl<T> Buffer<T> {
+    fn create(capacity: usize) -> *mut Buffer<T> {
+        debug_assert!(capacity.is_power_of_two());
+
+        let slots = if size_of::<T>() == 0 {
+            ptr::NonNull::dangling().as_ptr()
+        } else {
+            let layout = Layout::array::<MaybeUninit<T>>(capacity).unwrap();
+            let slots = unsafe { std::alloc::alloc(layout) as *mut MaybeUninit<T> };
+            if slots.is_null() {
+                std::alloc::handle_alloc_error(layout);
+            }
+            slots
+        };
+
+        Box::into_raw(Box::new(Buffer { slots, capacity }))
+    }
+
+    /// Deallocates the buffer without dropping any of the values in it
+    ///
+    /// # Safety
+    ///
+    /// The pointer must come from [Buffer::create] and must not be used afterwards.
+    unsafe fn destroy(ptr: *mut Buffer<T>) {
+        let buffer = Box::from_raw(ptr);
+        if size_of::<T>() != 0 {
+            let layout = Layout::array::<MaybeUninit<T>>(buffer.capacity).unwrap();
+            std::alloc::dealloc(buffer.slots as *mut u8, layout);
+        }
+    }
+
+    fn slot(&self, index: isize) -> *mut MaybeUninit<T> {
+        unsafe { self.slots.add(index as usize & (self.capacity - 1)) }
+    }
+
+    unsafe fn write(&self, index: isize, x: T) {
+        ptr::write(self.slot(index), MaybeUninit::new(x));
+    }
+
+    /// Copies the value out, which is only initialised when no other thread overwrote it
+    unsafe fn read(&self, index: isize) -> MaybeUninit<T> {
+        // a racing stealer may read a slot the worker is writing, and discards the copy when
+        // its compare-exchange fails, so the read must not assume anything about the value
+        ptr::read_volatile(self.slot(index))
+    }
+}
+
+impl<T> Worker<T> {
+    pub fn create() -> Worker<T> {
+        Worker {
+            inner: Arc::new(Inner {
+                top: AtomicIsize::new(0),
+                bottom: AtomicIsize::new(0),
+                buffer: AtomicPtr::new(Buffer::create(MIN_CAPACITY)),
+                active_stealers: AtomicUsize::new(0),
+                retired: UnsafeCell::new(Vec::new()),
+            }),
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Creates a thief for this deque, which can be cloned and sent to other threads
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    /// The number of values, which may already be outdated when stealers are active
+    pub fn len(&self) -> usize {
+        let bottom = self.inner.bottom.load(Ordering::Relaxed);
+        let top = self.inner.top.load(Ordering::Relaxed);
+
+        (bottom - top).max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push(&self, x: T) {
+        let inner = &*self.inner;
+        let bottom = inner.bottom.load(Ordering::Relaxed);
+        let top = inner.top.load(Ordering::Acquire);
+        let mut buffer = inner.buffer.load(Ordering::Relaxed);
+
+        if bottom - top >= unsafe { (*buffer).capacity } as isize {
+            buffer = self.grow(buffer, top, bottom);
+        }
+
+        unsafe { (*buffer).write(bottom, x) };
+        // publishes the value before the stealers can see the new bottom
+        fence(Ordering::Release);
+        inner.bottom.store(bottom + 1, Ordering::Relaxed);
+    }
+
+    /// Pops the most recently pushed value
+    pub fn pop(&self) -> Option<T> {
+        let inner = &*self.inner;
+        let bottom = inner.bottom.load(Ordering::Relaxed) - 1;
+        let buffer = inner.buffer.load(Ordering::Relaxed);
+
+        // claims the bottom slot before looking at top, racing stealers for the last value
+        inner.bottom.store(bottom, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        let top = inner.top.load(Ordering::Relaxed);
+
+        if top > bottom {
+            // the deque was empty
+            inner.bottom.store(bottom + 1, Ordering::Relaxed);
+            return None;
+        }
+
+        let x = unsafe { (*buffer).read(bottom) };
+        if top == bottom {
+            // the last value is also visible to stealers, so whoever moves top first gets it
+            let won = inner
+                .top
+                .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok();
+            inner.bottom.store(bottom + 1, Ordering::Relaxed);
+
+            if !won {
+                return None;
+            }
+        }
+
+        Some(unsafe { x.assume_init() })
+    }
+
+    /// Moves the values into a buffer of twice the capacity, retiring the old buffer
+    fn grow(&self, old: *mut Buffer<T>, top: isize, bottom: isize) -> *mut Buffer<T> {
+        let inner = &*self.inner;
+        let new = Buffer::create(unsafe { (*old).capacity } * 2);
+        for index in top..bottom {
+            unsafe { ptr::copy_nonoverlapping((*old).slot(index), (*new).slot(index), 1) };
+        }
+
+        inner.buffer.store(new, Ordering::SeqCst);
+
+        // stealers announce themselves before loading the buffer, so when none is active
+        // after the store above, later stealers can only see the new buffer
+        let retired = unsafe { &mut *inner.retired.get() };
+        retired.push(old);
+        if inner.active_stealers.load(Ordering::SeqCst) == 0 {
+            for buffer in retired.drain(..) {
+                unsafe { Buffer::destroy(buffer) };
+            }
+        }
+
+        new
+    }
+}
+
+impl<T> Stealer<T> {
+    pub fn is_empty(&self) -> bool {
+        let top = self.inner.top.load(Ordering::Acquire);
+        let bottom = self.inner.bottom.load(Ordering::Acquire);
+
+        bottom <= top
+    }
+
+    /// Takes the least recently pushed value
+    pub fn steal(&self) -> Steal<T> {
+        let inner = &*self.inner;
+        let top = inner.top.load(Ordering::Acquire);
+        fence(Ordering::SeqCst);
+        let bottom = inner.bottom.load(Ordering::Acquire);
+
+        if top >= bottom {
+            return Steal::Empty;
+        }
+
+        inner.active_stealers.fetch_add(1, Ordering::SeqCst);
+        let buffer = inner.buffer.load(Ordering::SeqCst);
+        let x = unsafe { (*buffer).read(top) };
+        inner.active_stealers.fetch_sub(1, Ordering::SeqCst);
+
+        match inner
+            .top
+            .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+        {
+            Ok(_) => Steal::Success(unsafe { x.assume_init() }),
+            Err(_) => Steal::Retry,
+        }
+    }
+}
+
+impl<T> Default for Worker<T> {
+    fn default() -> Self {
+        Worker::create()
+    }
+}
+
+impl<T> Clone for Stealer<T> {
+    fn clone(&self) -> Self {
+        Stealer {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        let top = *self.top.get_mut();
+        let bottom = *self.bottom.get_mut();
+        let buffer = *self.buffer.get_mut();
+
+        unsafe {
+            for index in top..bottom {
+                (*buffer).slot(index).read().assume_init_drop();
+            }
+            Buffer::destroy(buffer);
+
+            // retired buffers only hold copies of values that moved to a newer buffer
+            for retired in self.retired.get_mut().drain(..) {
+                Buffer::destroy(retired);
+            }
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque::work_stealing::{Steal, Worker};
+    use std::rc::Rc;
+
+    #[test]
+    fn the_worker_pops_the_most_recent_value() {
+        let worker = Worker::create();
+        worker.push(1);
+        worker.push(2);
+
+        assert_eq!(worker.len(), 2);
+        assert_eq!(worker.pop(), Some(2));
+        assert_eq!(worker.pop(), Some(1));
+        assert_eq!(worker.pop(), None);
+        assert!(worker.is_empty());
+    }
+
+    #[test]
+    fn stealers_take_the_oldest_value() {
+        let worker = Worker::create();
+        let stealer = worker.stealer();
+        worker.push(1);
+        worker.push(2);
+
+        assert_eq!(stealer.steal(), Steal::Success(1));
+        assert_eq!(stealer.clone().steal(), Steal::Success(2));
+        assert_eq!(stealer.steal(), Steal::Empty);
+        assert!(stealer.is_empty());
+    }
+
+    #[test]
+    fn the_buffer_grows_beyond_its_initial_capacity() {
+        let worker = Worker::create();
+        let stealer = worker.stealer();
+        for x in 0..100 {
+            worker.push(x);
+        }
+
+        assert_eq!(stealer.steal(), Steal::Success(0));
+        for x in (1..100).rev() {
+            assert_eq!(worker.pop(), Some(x));
+        }
+        assert_eq!(worker.pop(), None);
+    }
+
+    #[test]
+    fn the_buffer_wraps_around() {
+        let worker = Worker::create();
+        let stealer = worker.stealer();
+        for x in 0..1000 {
+            worker.push(x);
+            assert_eq!(stealer.steal(), Steal::Success(x));
+        }
+
+        assert!(worker.is_empty());
+    }
+
+    #[test]
+    fn remaining_values_are_dropped_once() {
+        let counter = Rc::new(());
+        {
+            let worker = Worker::create();
+            for _ in 0..40 {
+                worker.push(Rc::clone(&counter));
+            }
+            drop(worker.pop());
+        }
+
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn zero_sized_values_are_counted() {
+        let worker = Worker::create();
+        for _ in 0..40 {
+            worker.push(());
+        }
+
+        assert_eq!(worker.len(), 40);
+        assert_eq!(worker.stealer().steal(), Steal::Success(()));
+        assert_eq!(worker.pop(), Some(()));
+    }
+}
@@ -1 +1,54 @@
-pub mod deque;
\ No newline at end of file
+//! A doubly linked deque with O(1) pushes and pops at both ends.
+//!
+//! The most common items are re-exported from the crate root and from [prelude]:
+//!
+//! ```
+//! use double_linked_list::prelude::*;
+//!
+//! let mut data = deque![1, 2, 3];
+//! let handle = data.push_left(0);
+//!
+//! assert_eq!(data.get(handle), Some(&0));
+//! assert_eq!(data.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+//! ```
+//!
+//! An [Item] borrowed from a deque can walk to its neighbours, but only for as long as
+//! the deque is not changed. Use a [NodeHandle] to refer to an item across mutations.
+//!
+//! ```compile_fail
+//! use double_linked_list::prelude::*;
+//!
+//! let mut data = deque![1, 2];
+//! let first = data.first().unwrap();
+//! data.pop_left();
+//!
+//! assert_eq!(first.right().unwrap().value(), &2);
+//! ```
+//!
+//! The `proptest` feature exposes `deque::arbitrary_deque`, which generates random deques
+//! and operations for property tests in downstream crates.
+
+pub mod algorithms;
+pub mod deque;
+#[cfg(test)]
+mod test_helpers;
+
+pub use deque::arena::{ArenaDeque, ArenaHandle, ArenaPosition};
+pub use deque::blocking::{BlockingDeque, PopTimeoutError, PushError, TryPopError};
+pub use deque::bounded::{BoundedDeque, OverflowPolicy, Pushed};
+pub use deque::cursor::{Cursor, CursorMut};
+pub use deque::handle::NodeHandle;
+pub use deque::item::Item;
+pub use deque::iterator::{IntoIter, Iter, IterMut, Nodes};
+pub use deque::persistent::PersistentDeque;
+pub use deque::position::Position;
+pub use deque::retain::ExtractIf;
+pub use deque::unrolled::UnrolledDeque;
+pub use deque::work_stealing::{Steal, Stealer, Worker};
+pub use deque::Deque;
+
+/// Everything needed for everyday use of a [Deque], including the `deque!` macro
+pub mod prelude {
+    pub use crate::deque;
+    pub use crate::{Deque, NodeHandle, Position};
+}
@@ -0,0 +1,22 @@
+//! Helpers shared by the tests of the deques
+
+/// The values from left to right
+pub fn values<'a, T, D>(deque: &'a D) -> Vec<T>
+where
+    T: Clone + 'a,
+    &'a D: IntoIterator<Item = &'a T>,
+{
+    deque.into_iter().cloned().collect()
+}
+
+/// The values from left to right, collected from right to left to check the left links
+pub fn values_reversed<'a, T, D>(deque: &'a D) -> Vec<T>
+where
+    T: Clone + 'a,
+    &'a D: IntoIterator<Item = &'a T>,
+    <&'a D as IntoIterator>::IntoIter: DoubleEndedIterator,
+{
+    let mut reversed: Vec<T> = deque.into_iter().rev().cloned().collect();
+    reversed.reverse();
+    reversed
+}
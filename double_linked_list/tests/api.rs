@@ -0,0 +1,124 @@
+use double_linked_list::prelude::*;
+use double_linked_list::{Cursor, Item, Iter};
+use std::collections::HashSet;
+
+#[test]
+fn the_macro_is_usable_from_another_crate() {
+    let data = deque![1, 2, 3,];
+
+    assert_eq!(data.len(), 3);
+    assert_eq!(format!("{:?}", data), "[1, 2, 3]");
+}
+
+#[test]
+fn values_can_be_pushed_and_popped_at_both_ends() {
+    let mut data = Deque::create();
+    data.push_right(2);
+    data.push_left(1);
+    data.push_right(3);
+
+    assert_eq!(data.pop_left(), Some(1));
+    assert_eq!(data.pop_right(), Some(3));
+    assert_eq!(data.pop_right(), Some(2));
+    assert_eq!(data.pop_left(), None);
+}
+
+#[test]
+fn items_can_walk_to_their_neighbours() {
+    let data = deque!["a", "b", "c"];
+
+    let first: &Item<&str> = data.first().unwrap();
+    let middle = first.right().unwrap();
+
+    assert_eq!(middle.value(), &"b");
+    assert_eq!(middle.left().unwrap().value(), &"a");
+    assert_eq!(middle.right().unwrap().right(), None);
+}
+
+#[test]
+fn handles_outlive_mutations() {
+    let mut data = Deque::create();
+    let handle: NodeHandle = data.push_right(String::from("kept"));
+    data.push_left(String::from("other"));
+    data.push_right(String::from("other"));
+
+    *data.get_mut(handle).unwrap() += "!";
+    assert!(data.move_to_front(handle));
+
+    assert_eq!(data.remove(handle).as_deref(), Some("kept!"));
+    assert_eq!(data.get(handle), None);
+}
+
+#[test]
+fn positions_accept_indices_and_handles() {
+    fn read<P: Position>(data: &Deque<usize>, position: P) -> Option<usize> {
+        data.get(position).copied()
+    }
+
+    let mut data = deque![1, 2];
+    let handle = data.push_right(3);
+
+    assert_eq!(read(&data, 1), Some(2));
+    assert_eq!(read(&data, handle), Some(3));
+}
+
+#[test]
+fn iterators_work_from_both_ends() {
+    let mut data: Deque<usize> = (1..=4).collect();
+    for value in data.iter_mut() {
+        *value *= 10;
+    }
+
+    let iter: Iter<'_, usize> = data.iter();
+    assert_eq!(
+        iter.rev().copied().collect::<Vec<_>>(),
+        vec![40, 30, 20, 10]
+    );
+    assert_eq!(data.into_iter().collect::<Vec<_>>(), vec![10, 20, 30, 40]);
+}
+
+#[test]
+fn cursors_can_edit_in_the_middle() {
+    let mut data = deque![1, 3];
+    {
+        let mut cursor = data.cursor_first_mut();
+        cursor.insert_after(2);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+    }
+
+    let cursor: Cursor<'_, usize> = data.cursor_last();
+    assert_eq!(cursor.current(), Some(&2));
+    assert_eq!(data, deque![1, 2]);
+}
+
+#[test]
+fn deques_can_be_split_and_joined() {
+    let mut data = deque![1, 2, 3, 4];
+    let mut tail = data.split_off(2);
+    tail.append(&mut deque![5]);
+    data.prepend(&mut deque![0]);
+
+    assert_eq!(data, deque![0, 1, 2]);
+    assert_eq!(tail, deque![3, 4, 5]);
+}
+
+#[test]
+fn deques_work_with_std_collections() {
+    let mut set = HashSet::new();
+    set.insert(Deque::from(vec![1, 2]));
+
+    assert!(set.contains(&deque![1, 2]));
+    assert!(deque![1, 2] < deque![1, 3]);
+    assert_eq!(Deque::<usize>::default(), deque![]);
+}
+
+#[test]
+fn values_can_be_extracted() {
+    let mut data = deque![1, 2, 3, 4];
+    let even: Vec<_> = data.extract_if(|x| *x % 2 == 0).collect();
+
+    assert_eq!(even, vec![2, 4]);
+    assert_eq!(data, deque![1, 3]);
+}
@@ -0,0 +1,149 @@
+use double_linked_list::{BlockingDeque, PushError};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(not(miri))]
+const THREADS: usize = 4;
+#[cfg(miri)]
+const THREADS: usize = 2;
+
+#[cfg(not(miri))]
+const VALUES_PER_PRODUCER: usize = 10_000;
+#[cfg(miri)]
+const VALUES_PER_PRODUCER: usize = 20;
+
+#[test]
+fn every_value_is_consumed_exactly_once() {
+    let data = Arc::new(BlockingDeque::bounded(16));
+
+    let producers: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let data = Arc::clone(&data);
+            thread::spawn(move || {
+                for n in 0..VALUES_PER_PRODUCER {
+                    let value = t * VALUES_PER_PRODUCER + n;
+                    if n % 2 == 0 {
+                        data.push_left(value).unwrap();
+                    } else {
+                        data.push_right(value).unwrap();
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let consumers: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let data = Arc::clone(&data);
+            thread::spawn(move || {
+                let mut consumed = vec![];
+                loop {
+                    let popped = if t % 2 == 0 {
+                        data.pop_left()
+                    } else {
+                        data.pop_right()
+                    };
+                    match popped {
+                        Some(value) => consumed.push(value),
+                        None => return consumed,
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    data.close();
+
+    let mut seen = HashSet::new();
+    for consumer in consumers {
+        for value in consumer.join().unwrap() {
+            assert!(seen.insert(value), "{} was consumed twice", value);
+        }
+    }
+    assert_eq!(seen.len(), THREADS * VALUES_PER_PRODUCER);
+}
+
+#[test]
+fn the_capacity_is_never_exceeded() {
+    let data = Arc::new(BlockingDeque::bounded(2));
+
+    let producer = {
+        let data = Arc::clone(&data);
+        thread::spawn(move || {
+            for n in 0..VALUES_PER_PRODUCER {
+                data.push_right(n).unwrap();
+                assert!(data.len() <= 2);
+            }
+            data.close();
+        })
+    };
+
+    let mut expected = 0;
+    while let Some(value) = data.pop_left() {
+        assert_eq!(value, expected);
+        expected += 1;
+    }
+
+    producer.join().unwrap();
+    assert_eq!(expected, VALUES_PER_PRODUCER);
+}
+
+#[test]
+fn closing_wakes_waiting_consumers() {
+    let data: Arc<BlockingDeque<usize>> = Arc::new(BlockingDeque::create());
+
+    let consumers: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let data = Arc::clone(&data);
+            thread::spawn(move || data.pop_left())
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(10));
+    data.close();
+
+    for consumer in consumers {
+        assert_eq!(consumer.join().unwrap(), None);
+    }
+}
+
+#[test]
+fn closing_wakes_waiting_producers() {
+    let data = Arc::new(BlockingDeque::bounded(1));
+    data.push_right(0).unwrap();
+
+    let producers: Vec<_> = (1..=THREADS)
+        .map(|n| {
+            let data = Arc::clone(&data);
+            thread::spawn(move || data.push_right(n))
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(10));
+    data.close();
+
+    for (n, producer) in (1..=THREADS).zip(producers) {
+        assert_eq!(producer.join().unwrap(), Err(PushError(n)));
+    }
+    assert_eq!(data.pop_left(), Some(0));
+}
+
+#[test]
+fn a_waiting_consumer_receives_a_later_push() {
+    let data = Arc::new(BlockingDeque::create());
+
+    let consumer = {
+        let data = Arc::clone(&data);
+        thread::spawn(move || data.pop_right_timeout(Duration::from_secs(10)))
+    };
+
+    thread::sleep(Duration::from_millis(10));
+    data.push_left(String::from("late")).unwrap();
+
+    assert_eq!(consumer.join().unwrap().as_deref(), Ok("late"));
+}
@@ -0,0 +1,95 @@
+use double_linked_list::{Steal, Worker};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+#[cfg(not(miri))]
+const THIEVES: usize = 4;
+#[cfg(miri)]
+const THIEVES: usize = 2;
+
+#[cfg(not(miri))]
+const VALUES: usize = 100_000;
+#[cfg(miri)]
+const VALUES: usize = 200;
+
+#[test]
+fn every_value_is_consumed_exactly_once() {
+    let worker = Worker::create();
+    let done = Arc::new(AtomicBool::new(false));
+
+    let thieves: Vec<_> = (0..THIEVES)
+        .map(|_| {
+            let stealer = worker.stealer();
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut stolen = vec![];
+                loop {
+                    match stealer.steal() {
+                        Steal::Success(value) => stolen.push(value),
+                        Steal::Retry => {}
+                        Steal::Empty if done.load(Ordering::Acquire) => return stolen,
+                        Steal::Empty => thread::yield_now(),
+                    }
+                }
+            })
+        })
+        .collect();
+
+    // mixes pushes and pops, so the buffer grows and the last value is contended
+    let mut popped = vec![];
+    for value in 0..VALUES {
+        worker.push(value);
+        if value % 3 == 0 {
+            popped.extend(worker.pop());
+        }
+    }
+    while let Some(value) = worker.pop() {
+        popped.push(value);
+    }
+    done.store(true, Ordering::Release);
+
+    let mut seen = vec![false; VALUES];
+    let stolen = thieves.into_iter().flat_map(|t| t.join().unwrap());
+    for value in popped.into_iter().chain(stolen) {
+        assert!(!seen[value], "{} was consumed twice", value);
+        seen[value] = true;
+    }
+    assert!(seen.iter().all(|consumed| *consumed));
+}
+
+#[test]
+fn boxed_values_survive_growing_while_being_stolen() {
+    let worker: Worker<Box<usize>> = Worker::create();
+    let stealer = worker.stealer();
+
+    let thief = thread::spawn(move || {
+        let mut sum = 0;
+        let mut empty_in_a_row = 0;
+        while empty_in_a_row < 1_000 {
+            match stealer.steal() {
+                Steal::Success(value) => {
+                    sum += *value;
+                    empty_in_a_row = 0;
+                }
+                Steal::Retry => {}
+                Steal::Empty => {
+                    empty_in_a_row += 1;
+                    thread::yield_now();
+                }
+            }
+        }
+        sum
+    });
+
+    for value in 0..VALUES {
+        worker.push(Box::new(value));
+    }
+    let stolen = thief.join().unwrap();
+
+    let mut remaining = 0;
+    while let Some(value) = worker.pop() {
+        remaining += *value;
+    }
+    assert_eq!(stolen + remaining, (0..VALUES).sum::<usize>());
+}
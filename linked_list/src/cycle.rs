@@ -0,0 +1,211 @@
+//! Cycle detection in sequences defined by a successor function.
+//!
+//! A linked list is followed from node to node with `successor`, which returns `None` at
+//! the end of the list. Nodes can be anything comparable, such as indices into an arena
+//! of `next` links or pointers to nodes.
+//!
+//! ```
+//! use linked_list::cycle::{floyd, Cycle};
+//!
+//! // 0 -> 1 -> 2 -> 3 -> 1
+//! let next = [Some(1), Some(2), Some(3), Some(1)];
+//! let cycle = floyd(0, |&i: &usize| next[i]);
+//!
+//! assert_eq!(cycle, Some(Cycle { start: 1, len: 3, tail_len: 1 }));
+//! ```
+
+/// A cycle found in a sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N> {
+    /// The first node of the sequence that is on the cycle
+    pub start: N,
+    /// The number of nodes on the cycle
+    pub len: usize,
+    /// The number of nodes before the cycle starts
+    pub tail_len: usize,
+}
+
+/// Advances a node `n` steps, or returns `None` when the sequence ends first
+fn advance<N, F>(mut node: N, n: usize, successor: &mut F) -> Option<N>
+where
+    F: FnMut(&N) -> Option<N>,
+{
+    for _ in 0..n {
+        node = successor(&node)?;
+    }
+
+    Some(node)
+}
+
+/// Finds where the cycle starts, given that it is `len` nodes long
+fn locate<N, F>(first: N, len: usize, mut successor: F) -> Option<Cycle<N>>
+where
+    N: Clone + PartialEq,
+    F: FnMut(&N) -> Option<N>,
+{
+    // a pointer `len` nodes ahead meets the trailing pointer at the start of the cycle
+    let mut trailing = first.clone();
+    let mut leading = advance(first, len, &mut successor)?;
+    let mut tail_len = 0;
+    while trailing != leading {
+        trailing = successor(&trailing)?;
+        leading = successor(&leading)?;
+        tail_len += 1;
+    }
+
+    Some(Cycle {
+        start: trailing,
+        len,
+        tail_len,
+    })
+}
+
+/// Floyd's tortoise and hare: finds the cycle reachable from `first`, if there is one
+///
+/// Takes O(tail_len + len) steps and O(1) memory.
+pub fn floyd<N, F>(first: N, mut successor: F) -> Option<Cycle<N>>
+where
+    N: Clone + PartialEq,
+    F: FnMut(&N) -> Option<N>,
+{
+    // the hare gains one node per step, so it meets the tortoise somewhere on the cycle
+    let mut tortoise = successor(&first)?;
+    let mut hare = advance(first.clone(), 2, &mut successor)?;
+    while tortoise != hare {
+        tortoise = successor(&tortoise)?;
+        hare = advance(hare, 2, &mut successor)?;
+    }
+
+    // walking once around from the meeting point gives the length of the cycle
+    let mut len = 1;
+    let mut node = successor(&tortoise)?;
+    while node != tortoise {
+        node = successor(&node)?;
+        len += 1;
+    }
+
+    locate(first, len, successor)
+}
+
+/// Brent's algorithm: finds the cycle reachable from `first`, if there is one
+///
+/// Finds the length of the cycle directly, with fewer calls to `successor` than [floyd].
+pub fn brent<N, F>(first: N, mut successor: F) -> Option<Cycle<N>>
+where
+    N: Clone + PartialEq,
+    F: FnMut(&N) -> Option<N>,
+{
+    // the tortoise teleports to the hare at every power of two, until the hare runs into it
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = first.clone();
+    let mut hare = successor(&first)?;
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = successor(&hare)?;
+        len += 1;
+    }
+
+    locate(first, len, successor)
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::cycle::{brent, floyd, Cycle};
+
+        #[test]
+        fn a_list_without_a_cycle() {
+            let next = [Some(1), Some(2), None];
+
+            assert_eq!(floyd(0, |&i: &usize| next[i]), None);
+            assert_eq!(brent(0, |&i: &usize| next[i]), None);
+        }
+
+        #[test]
+        fn a_node_pointing_at_itself() {
+            let expected = Some(Cycle {
+                start: 7,
+                len: 1,
+                tail_len: 0,
+            });
+
+            assert_eq!(floyd(7, |&i: &usize| Some(i)), expected);
+            assert_eq!(brent(7, |&i: &usize| Some(i)), expected);
+        }
+
+        #[test]
+        fn a_cycle_behind_a_tail() {
+            // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2
+            let next = |&i: &usize| Some(if i == 5 { 2 } else { i + 1 });
+            let expected = Some(Cycle {
+                start: 2,
+                len: 4,
+                tail_len: 2,
+            });
+
+            assert_eq!(floyd(0, next), expected);
+            assert_eq!(brent(0, next), expected);
+        }
+
+        #[test]
+        fn a_cycle_of_values_rather_than_indices() {
+            // x -> x² + 1 mod 255 must eventually repeat
+            let next = |x: &u64| Some((x * x + 1) % 255);
+
+            let cycle = floyd(3, next).unwrap();
+            assert_eq!(brent(3, next), Some(cycle.clone()));
+
+            let after_a_lap = (0..cycle.len).fold(cycle.start, |x, _| next(&x).unwrap());
+            assert_eq!(after_a_lap, cycle.start);
+        }
+    }
+
+    mod property_tests {
+        use crate::cycle::{brent, floyd, Cycle};
+        use proptest::prelude::*;
+        use std::collections::HashMap;
+
+        /// Remembers every node visited, which is slow but obviously correct
+        fn visiting(next: &[Option<usize>]) -> Option<Cycle<usize>> {
+            let mut visited = HashMap::new();
+            let mut node = 0;
+            loop {
+                if let Some(&step) = visited.get(&node) {
+                    return Some(Cycle {
+                        start: node,
+                        len: visited.len() - step,
+                        tail_len: step,
+                    });
+                }
+                visited.insert(node, visited.len());
+                node = next[node]?;
+            }
+        }
+
+        fn arena() -> impl Strategy<Value = Vec<Option<usize>>> {
+            (1..50usize).prop_flat_map(|len| {
+                prop::collection::vec(prop::option::weighted(0.95, 0..len), len)
+            })
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn floyd_matches_remembering_the_nodes(next in arena()) {
+                prop_assert_eq!(floyd(0, |&i: &usize| next[i]), visiting(&next));
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn brent_matches_remembering_the_nodes(next in arena()) {
+                prop_assert_eq!(brent(0, |&i: &usize| next[i]), visiting(&next));
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Singly linked lists: an owned [List] and a [PersistentList] whose versions share
+//! their tails.
+//!
+//! ```
+//! use linked_list::{list, List};
+//!
+//! let mut data: List<usize> = list![1, 2, 3];
+//! data.push_front(0);
+//!
+//! assert_eq!(data.peek(), Some(&0));
+//! assert_eq!(data.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+//! ```
+
+pub mod cycle;
+pub mod list;
+pub mod persistent;
+pub mod self_organizing;
+#[cfg(test)]
+mod test_helpers;
+pub mod treiber_stack;
+
+pub use list::iterator::{IntoIter, Iter, IterMut};
+pub use list::List;
+pub use persistent::rc::PersistentList;
+pub use persistent::sync::PersistentList as ArcPersistentList;
+pub use self_organizing::{
+    AccessStrategy, FrequencyCount, MoveToFront, SelfOrganizingList, Statistics, Transpose,
+};
+pub use treiber_stack::TreiberStack;
@@ -0,0 +1,197 @@
+mod algorithms;
+pub mod iterator;
+mod macros;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+/// A singly linked list of owned nodes, with O(1) pushes and pops at the front
+pub struct List<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> List<T> {
+    pub fn create() -> List<T> {
+        List { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, x: T) {
+        let next = self.head.take();
+        self.head = Some(Box::new(Node { value: x, next }));
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.take().map(|node| {
+            self.head = node.next;
+            self.len -= 1;
+
+            node.value
+        })
+    }
+
+    /// The value at the front
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+
+    /// Reverses the list in place by relinking its nodes, in O(n)
+    pub fn reverse(&mut self) {
+        self.head = reverse_link(self.head.take());
+    }
+}
+
+fn reverse_link<T>(mut rest: Link<T>) -> Link<T> {
+    let mut reversed = None;
+    while let Some(mut node) = rest {
+        rest = node.next.take();
+        node.next = reversed;
+        reversed = Some(node);
+    }
+
+    reversed
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        // dropping the head would drop the rest of the list recursively, one stack frame
+        // per node, so the nodes are unlinked one by one instead
+        let mut rest = self.head.take();
+        while let Some(mut node) = rest {
+            rest = node.next.take();
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::create()
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    /// Keeps the order of the values, so the first value ends up at the front
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::create();
+        let mut tail = &mut list.head;
+        for value in iter {
+            let node = tail.insert(Box::new(Node { value, next: None }));
+            tail = &mut node.next;
+            list.len += 1;
+        }
+
+        list
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list::List;
+
+    #[test]
+    fn pushing_and_popping_at_the_front() {
+        let mut data = List::create();
+        data.push_front(1);
+        data.push_front(2);
+
+        assert_eq!(data.len(), 2);
+        assert_eq!(data.pop_front(), Some(2));
+        assert_eq!(data.pop_front(), Some(1));
+        assert_eq!(data.pop_front(), None);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn peeking_at_the_front() {
+        let mut data: List<usize> = List::create();
+        assert_eq!(data.peek(), None);
+
+        data.push_front(1);
+        if let Some(value) = data.peek_mut() {
+            *value = 5;
+        }
+        assert_eq!(data.peek(), Some(&5));
+    }
+
+    #[test]
+    fn reversing_relinks_the_nodes() {
+        let mut data: List<usize> = (0..5).collect();
+        data.reverse();
+
+        assert_eq!(
+            data.iter().copied().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1, 0]
+        );
+        assert_eq!(data.len(), 5);
+    }
+
+    #[test]
+    fn reversing_an_empty_list() {
+        let mut data: List<usize> = List::create();
+        data.reverse();
+
+        assert_eq!(data, List::create());
+    }
+
+    #[test]
+    fn collecting_keeps_the_order() {
+        let data: List<usize> = vec![1, 2, 3].into_iter().collect();
+
+        assert_eq!(data.peek(), Some(&1));
+        assert_eq!(data.len(), 3);
+        assert_eq!(format!("{:?}", data), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn clones_are_equal_but_independent() {
+        let data: List<usize> = (0..3).collect();
+        let mut copy = data.clone();
+        assert_eq!(data, copy);
+
+        copy.pop_front();
+        assert_ne!(data, copy);
+    }
+
+    #[test]
+    fn dropping_a_million_nodes_does_not_overflow_the_stack() {
+        let data: List<usize> = (0..1_000_000).collect();
+        drop(data);
+    }
+}
@@ -0,0 +1,532 @@
+use crate::list::{reverse_link, Link, List};
+
+/// Cuts a list after `at` nodes, returning the rest, or `None` when it is not that long
+fn split_link<T>(mut link: &mut Link<T>, at: usize) -> Link<T> {
+    for _ in 0..at {
+        link = &mut link.as_mut()?.next;
+    }
+
+    link.take()
+}
+
+/// Merges two sorted lists by relinking their nodes, taking from `a` first on ties
+fn merge_links<T: Ord>(mut a: Link<T>, mut b: Link<T>) -> Link<T> {
+    let mut head = None;
+    let mut tail = &mut head;
+    while let (Some(x), Some(y)) = (&a, &b) {
+        let side = if y.value < x.value { &mut b } else { &mut a };
+        let mut node = side.take().unwrap();
+        *side = node.next.take();
+        tail = &mut tail.insert(node).next;
+    }
+    *tail = a.or(b);
+
+    head
+}
+
+/// Sorts a list of `len` nodes top-down, which recurses only O(log n) deep
+fn sort_link<T: Ord>(mut link: Link<T>, len: usize) -> Link<T> {
+    if len < 2 {
+        return link;
+    }
+
+    let half = len / 2;
+    let rest = split_link(&mut link, half);
+    merge_links(sort_link(link, half), sort_link(rest, len - half))
+}
+
+impl<T> List<T> {
+    /// The value at index `len / 2`, found by walking a second pointer at double speed
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = self.head.as_deref();
+        while let Some(next) = fast.and_then(|node| node.next.as_deref()) {
+            slow = slow.next.as_deref()?;
+            fast = next.next.as_deref();
+        }
+
+        Some(&slow.value)
+    }
+
+    /// The value `k` places before the last value, so `kth_from_end(0)` is the last value
+    ///
+    /// A leading pointer walks `k` nodes ahead, so the list is traversed once.
+    pub fn kth_from_end(&self, k: usize) -> Option<&T> {
+        let mut lead = self.head.as_deref();
+        for _ in 0..k {
+            lead = lead?.next.as_deref();
+        }
+
+        let mut lead = lead?;
+        let mut trail = self.head.as_deref()?;
+        while let Some(next) = lead.next.as_deref() {
+            lead = next;
+            trail = trail.next.as_deref()?;
+        }
+
+        Some(&trail.value)
+    }
+
+    /// Reverses every consecutive group of `k` values, leaving a shorter last group as is
+    ///
+    /// # Panics
+    ///
+    /// Panics when `k` is 0.
+    pub fn reverse_in_groups(&mut self, k: usize) {
+        assert!(k > 0, "Cannot reverse groups of 0 values");
+
+        let mut remaining = self.len;
+        let mut rest = self.head.take();
+        let mut tail = &mut self.head;
+        while remaining >= k {
+            let after = split_link(&mut rest, k);
+            *tail = reverse_link(rest);
+            for _ in 0..k {
+                tail = &mut tail.as_mut().unwrap().next;
+            }
+
+            rest = after;
+            remaining -= k;
+        }
+        *tail = rest;
+    }
+
+    /// Whether the values read the same in both directions
+    ///
+    /// The first half is pushed onto a stack while a second pointer runs to the end at
+    /// double speed, after which the second half is compared against the stack.
+    pub fn is_palindrome(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut first_half = vec![];
+        let mut slow = self.head.as_deref();
+        let mut fast = self.head.as_deref();
+        while let (Some(current), Some(runner)) = (slow, fast) {
+            match runner.next.as_deref() {
+                Some(next) => {
+                    first_half.push(&current.value);
+                    slow = current.next.as_deref();
+                    fast = next.next.as_deref();
+                }
+                None => {
+                    // an odd length, so the middle value is compared with nothing
+                    slow = current.next.as_deref();
+                    break;
+                }
+            }
+        }
+
+        while let Some(current) = slow {
+            if first_half.pop() != Some(&current.value) {
+                return false;
+            }
+            slow = current.next.as_deref();
+        }
+
+        true
+    }
+
+    /// Removes consecutive repeated values, keeping the first of each run
+    ///
+    /// Like `Vec::dedup`, this only removes every duplicate when the list is sorted. See
+    /// [List::dedup_unsorted] for removing repeats that are not next to each other.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut current = &mut self.head;
+        while let Some(node) = current {
+            while node
+                .next
+                .as_ref()
+                .is_some_and(|next| next.value == node.value)
+            {
+                let removed = node.next.take().unwrap();
+                node.next = removed.next;
+                self.len -= 1;
+            }
+            current = &mut node.next;
+        }
+    }
+
+    /// Removes every value equal to an earlier one, keeping the first of each
+    ///
+    /// Each value is compared with every value after it, so this takes O(n²) comparisons
+    /// but only needs `PartialEq`. Sorting and calling [List::dedup] takes O(n log n).
+    pub fn dedup_unsorted(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut current = self.head.as_deref_mut();
+        while let Some(node) = current {
+            let mut runner = &mut node.next;
+            loop {
+                match runner {
+                    None => break,
+                    Some(next) if next.value == node.value => {
+                        *runner = next.next.take();
+                        self.len -= 1;
+                    }
+                    Some(next) => runner = &mut next.next,
+                }
+            }
+            current = node.next.as_deref_mut();
+        }
+    }
+
+    /// Sorts the list in place with a stable merge sort, in O(n log n)
+    ///
+    /// The nodes are relinked rather than moved, so sorting does not allocate.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.head = sort_link(self.head.take(), self.len);
+    }
+
+    /// Merges another sorted list into this sorted list, leaving `other` empty
+    ///
+    /// The nodes are relinked rather than moved, so merging takes O(n + m) comparisons and
+    /// no allocations. Values of this list come before equal values of `other`.
+    pub fn merge(&mut self, other: &mut List<T>)
+    where
+        T: Ord,
+    {
+        self.head = merge_links(self.head.take(), other.head.take());
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+// the intersection point needs lists that can share nodes, which only persistent lists do
+macro_rules! intersection {
+    ($list: ty) => {
+        impl<T> $list {
+            /// The tail that this list shares with `other`, which is empty when they share no
+            /// nodes
+            ///
+            /// Lists that share nodes end in the same nodes, so the longer list skips its extra
+            /// values, after which both lists are walked in step until they reach the same node.
+            /// This takes O(n + m), comparing the addresses of values rather than the values.
+            pub fn intersection(&self, other: &Self) -> Self {
+                let (len, other_len) = (self.len(), other.len());
+                let mut list = self.clone();
+                for _ in other_len..len {
+                    list = list.tail();
+                }
+                let mut other = other.clone();
+                for _ in len..other_len {
+                    other = other.tail();
+                }
+
+                while let (Some(a), Some(b)) = (list.head(), other.head()) {
+                    if std::ptr::eq(a, b) {
+                        return list;
+                    }
+                    list = list.tail();
+                    other = other.tail();
+                }
+
+                Self::create()
+            }
+        }
+    };
+}
+
+intersection!(crate::persistent::rc::PersistentList<T>);
+intersection!(crate::persistent::sync::PersistentList<T>);
+
+#[cfg(test)]
+mod tests {
+
+    mod unit_tests {
+        use crate::list;
+        use crate::list::List;
+        use crate::persistent::rc::PersistentList;
+        use crate::persistent::sync;
+        use crate::test_helpers::values;
+
+        #[test]
+        fn the_middle_of_an_even_length_is_the_second_of_both() {
+            assert_eq!(list![1, 2, 3, 4].middle(), Some(&3));
+            assert_eq!(list![1, 2, 3].middle(), Some(&2));
+            assert_eq!(List::<usize>::create().middle(), None);
+        }
+
+        #[test]
+        fn counting_from_the_end() {
+            let data = list![1, 2, 3];
+
+            assert_eq!(data.kth_from_end(0), Some(&3));
+            assert_eq!(data.kth_from_end(2), Some(&1));
+            assert_eq!(data.kth_from_end(3), None);
+        }
+
+        #[test]
+        fn reversing_groups_leaves_a_short_last_group() {
+            let mut data: List<usize> = (1..=8).collect();
+            data.reverse_in_groups(3);
+
+            assert_eq!(values(&data), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+            assert_eq!(data.len(), 8);
+        }
+
+        #[test]
+        #[should_panic]
+        fn reversing_empty_groups_is_refused() {
+            list![1].reverse_in_groups(0);
+        }
+
+        #[test]
+        fn palindromes_of_odd_and_even_length() {
+            assert!(list![1, 2, 1].is_palindrome());
+            assert!(list![1, 2, 2, 1].is_palindrome());
+            assert!(!list![1, 2].is_palindrome());
+            assert!(List::<usize>::create().is_palindrome());
+        }
+
+        #[test]
+        fn dedup_keeps_one_of_each_run() {
+            let mut data = list![1, 1, 2, 3, 3, 3, 1];
+            data.dedup();
+
+            assert_eq!(values(&data), vec![1, 2, 3, 1]);
+            assert_eq!(data.len(), 4);
+        }
+
+        #[test]
+        fn dedup_unsorted_keeps_the_first_of_each_value() {
+            let mut data = list![1, 1, 2, 3, 1, 3, 4, 2];
+            data.dedup_unsorted();
+
+            assert_eq!(values(&data), vec![1, 2, 3, 4]);
+            assert_eq!(data.len(), 4);
+        }
+
+        #[test]
+        fn merging_sorted_lists() {
+            let mut data = list![1, 4, 6];
+            let mut other = list![2, 3, 7];
+            data.merge(&mut other);
+
+            assert_eq!(values(&data), vec![1, 2, 3, 4, 6, 7]);
+            assert_eq!(data.len(), 6);
+            assert!(other.is_empty());
+        }
+
+        #[test]
+        fn sorting_relinks_the_nodes() {
+            let mut data: List<usize> = list![5, 1, 4, 2, 3];
+            let first = data.peek().unwrap() as *const usize;
+            data.sort();
+
+            assert_eq!(values(&data), vec![1, 2, 3, 4, 5]);
+            assert_eq!(data.kth_from_end(0).unwrap() as *const usize, first);
+        }
+
+        #[test]
+        fn lists_with_equal_values_but_no_shared_nodes_do_not_intersect() {
+            let a: PersistentList<usize> = (0..3).collect();
+            let b: PersistentList<usize> = (0..3).collect();
+
+            assert!(a.intersection(&b).is_empty());
+        }
+
+        #[test]
+        fn lists_intersect_where_they_start_sharing_nodes() {
+            let shared: PersistentList<usize> = (5..8).collect();
+            let a = shared.prepend(2).prepend(1);
+            let b = shared.prepend(1);
+
+            let intersection = a.intersection(&b);
+            assert_eq!(intersection, shared);
+            assert!(std::ptr::eq(
+                intersection.head().unwrap(),
+                shared.head().unwrap()
+            ));
+        }
+
+        #[test]
+        fn shared_lists_intersect_across_threads() {
+            let shared: sync::PersistentList<usize> = (5..8).collect();
+            let a = shared.prepend(2).prepend(1);
+            let b = std::thread::spawn({
+                let shared = shared.clone();
+                move || shared.prepend(1)
+            })
+            .join()
+            .unwrap();
+
+            let intersection = a.intersection(&b);
+            assert_eq!(intersection, shared);
+            assert!(std::ptr::eq(
+                intersection.head().unwrap(),
+                shared.head().unwrap()
+            ));
+            assert!(a.intersection(&(5..8).collect()).is_empty());
+        }
+    }
+
+    mod property_tests {
+        use crate::list::List;
+        use crate::persistent::rc::PersistentList;
+        use crate::test_helpers::values;
+        use proptest::prelude::*;
+
+        fn prepend_all(values: &[u8], list: &PersistentList<u8>) -> PersistentList<u8> {
+            values
+                .iter()
+                .rev()
+                .fold(list.clone(), |list, x| list.prepend(*x))
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn the_middle_is_at_half_the_length(data: Vec<u8>) {
+                let list: List<u8> = data.iter().copied().collect();
+                prop_assert_eq!(list.middle(), data.get(data.len() / 2));
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn kth_from_end_matches_indexing(data: Vec<u8>, k in 0..20usize) {
+                let list: List<u8> = data.iter().copied().collect();
+                let expected = data.len().checked_sub(k + 1).map(|index| &data[index]);
+                prop_assert_eq!(list.kth_from_end(k), expected);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn reversing_groups_matches_reversed_chunks(data: Vec<u8>, k in 1..10usize) {
+                let mut list: List<u8> = data.iter().copied().collect();
+                list.reverse_in_groups(k);
+
+                let expected: Vec<u8> = data
+                    .chunks(k)
+                    .flat_map(|chunk| {
+                        let mut chunk = chunk.to_vec();
+                        if chunk.len() == k {
+                            chunk.reverse();
+                        }
+                        chunk
+                    })
+                    .collect();
+                prop_assert_eq!(values(&list), expected);
+                prop_assert_eq!(list.len(), data.len());
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn palindromes_read_the_same_reversed(data: Vec<bool>) {
+                let list: List<bool> = data.iter().copied().collect();
+                let reversed: Vec<bool> = data.iter().rev().copied().collect();
+                prop_assert_eq!(list.is_palindrome(), data == reversed);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn mirrored_values_are_palindromes(data: Vec<u8>, middle: Option<u8>) {
+                let list: List<u8> = data
+                    .iter()
+                    .chain(middle.iter())
+                    .chain(data.iter().rev())
+                    .copied()
+                    .collect();
+                prop_assert!(list.is_palindrome());
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn dedup_matches_vec(mut data: Vec<bool>) {
+                let mut list: List<bool> = data.iter().copied().collect();
+                list.dedup();
+                data.dedup();
+
+                prop_assert_eq!(values(&list), data.clone());
+                prop_assert_eq!(list.len(), data.len());
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn dedup_unsorted_keeps_first_occurrences(data: Vec<u8>) {
+                let mut list: List<u8> = data.iter().map(|x| x % 8).collect();
+                list.dedup_unsorted();
+
+                let mut expected: Vec<u8> = vec![];
+                for x in data.iter().map(|x| x % 8) {
+                    if !expected.contains(&x) {
+                        expected.push(x);
+                    }
+                }
+                prop_assert_eq!(values(&list), expected.clone());
+                prop_assert_eq!(list.len(), expected.len());
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn sorting_matches_vec(mut data: Vec<u8>) {
+                let mut list: List<u8> = data.iter().copied().collect();
+                list.sort();
+                data.sort();
+
+                prop_assert_eq!(values(&list), data);
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn sorting_is_stable(data: Vec<u8>) {
+                // only the keys are compared, so equal keys keep their original order
+                #[derive(Debug, Clone, PartialEq, Eq)]
+                struct Keyed(u8, usize);
+                impl PartialOrd for Keyed {
+                    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                        Some(self.cmp(other))
+                    }
+                }
+                impl Ord for Keyed {
+                    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                        self.0.cmp(&other.0)
+                    }
+                }
+
+                let keyed: Vec<Keyed> = data.iter().enumerate().map(|(i, key)| Keyed(key % 4, i)).collect();
+                let mut list: List<Keyed> = keyed.iter().cloned().collect();
+                list.sort();
+                let mut expected = keyed;
+                expected.sort_by_key(|k| k.0);
+
+                prop_assert_eq!(list.iter().map(|k| k.1).collect::<Vec<_>>(), expected.iter().map(|k| k.1).collect::<Vec<_>>());
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn merging_sorted_lists_matches_sorting_both(mut a: Vec<u8>, mut b: Vec<u8>) {
+                a.sort();
+                b.sort();
+                let mut list: List<u8> = a.iter().copied().collect();
+                let mut other: List<u8> = b.iter().copied().collect();
+                list.merge(&mut other);
+
+                let mut expected = [a, b].concat();
+                expected.sort();
+                prop_assert_eq!(values(&list), expected.clone());
+                prop_assert_eq!(list.len(), expected.len());
+                prop_assert!(other.is_empty());
+            }
+
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn lists_intersect_at_their_shared_tail(a: Vec<u8>, b: Vec<u8>, shared: Vec<u8>) {
+                let shared: PersistentList<u8> = shared.into_iter().collect();
+                let (a, b) = (prepend_all(&a, &shared), prepend_all(&b, &shared));
+
+                for intersection in [a.intersection(&b), b.intersection(&a)] {
+                    prop_assert_eq!(intersection.len(), shared.len());
+                    prop_assert_eq!(intersection.head().map(|x| x as *const u8), shared.head().map(|x| x as *const u8));
+                }
+            }
+        }
+    }
+}
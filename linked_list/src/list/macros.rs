@@ -0,0 +1,32 @@
+/// Creates a [List](crate::list::List) holding the values in the given order
+#[macro_export]
+macro_rules! list {
+    ( $( $x:expr ),* $(,)? ) => {
+        <$crate::list::List<_> as ::core::iter::FromIterator<_>>::from_iter([$( $x ),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list::List;
+
+    #[test]
+    fn create_empty_list() {
+        let data: List<usize> = list![];
+        assert_eq!(data.peek(), None);
+        assert_eq!(data.len(), 0);
+    }
+
+    #[test]
+    fn create_list_keeps_the_order() {
+        let data = list![1, 2, 4];
+        assert_eq!(data.peek(), Some(&1));
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn create_list_with_trailing_comma() {
+        let data = list![1, 2,];
+        assert_eq!(data.len(), 2);
+    }
+}
@@ -0,0 +1,149 @@
+//! Immutable cons lists whose versions share their tails.
+//!
+//! The list exists in two variants that only differ in how their nodes are shared:
+//! [rc::PersistentList] uses `Rc`, and [sync::PersistentList] uses `Arc` so that lists can
+//! be shared between threads.
+
+// defines a persistent list sharing its nodes through `$pointer`, which is `Rc` or `Arc`
+macro_rules! persistent_list {
+    ($pointer: ident) => {
+        type Link<T> = Option<$pointer<Node<T>>>;
+
+        struct Node<T> {
+            value: T,
+            next: Link<T>,
+        }
+
+        /// An immutable singly linked list, where prepending returns a new list that
+        /// shares all nodes of the original.
+        ///
+        /// Cloning a list, taking its tail and prepending to it all take O(1).
+        pub struct PersistentList<T> {
+            head: Link<T>,
+        }
+
+        impl<T> PersistentList<T> {
+            pub fn create() -> PersistentList<T> {
+                PersistentList { head: None }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.head.is_none()
+            }
+
+            /// The number of values, which takes O(n)
+            pub fn len(&self) -> usize {
+                self.iter().count()
+            }
+
+            /// A new list with `x` in front of the values of this list
+            pub fn prepend(&self, x: T) -> PersistentList<T> {
+                PersistentList {
+                    head: Some($pointer::new(Node {
+                        value: x,
+                        next: self.head.clone(),
+                    })),
+                }
+            }
+
+            /// The first value
+            pub fn head(&self) -> Option<&T> {
+                self.head.as_ref().map(|node| &node.value)
+            }
+
+            /// The list without its first value, which is empty when this list is empty
+            pub fn tail(&self) -> PersistentList<T> {
+                PersistentList {
+                    head: self.head.as_ref().and_then(|node| node.next.clone()),
+                }
+            }
+
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter {
+                    next: self.head.as_deref(),
+                }
+            }
+        }
+
+        impl<T> Drop for PersistentList<T> {
+            fn drop(&mut self) {
+                // unlinks the nodes one by one instead of recursing, stopping at the first
+                // node that another list still refers to; `into_inner` hands a node to exactly
+                // one of its last owners, even when several threads release it at once
+                let mut rest = self.head.take();
+                while let Some(mut node) = rest.and_then($pointer::into_inner) {
+                    rest = node.next.take();
+                }
+            }
+        }
+
+        impl<T> Clone for PersistentList<T> {
+            fn clone(&self) -> Self {
+                PersistentList {
+                    head: self.head.clone(),
+                }
+            }
+        }
+
+        impl<T> Default for PersistentList<T> {
+            fn default() -> Self {
+                PersistentList::create()
+            }
+        }
+
+        impl<T: std::fmt::Debug> std::fmt::Debug for PersistentList<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for PersistentList<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.iter().eq(other.iter())
+            }
+        }
+
+        impl<T: Eq> Eq for PersistentList<T> {}
+
+        impl<T> FromIterator<T> for PersistentList<T> {
+            /// Keeps the order of the values, so the first value ends up at the head
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                let values: Vec<T> = iter.into_iter().collect();
+                let mut head = None;
+                for value in values.into_iter().rev() {
+                    head = Some($pointer::new(Node { value, next: head }));
+                }
+
+                PersistentList { head }
+            }
+        }
+
+        /// Iterates over the values of a list, see [PersistentList::iter]
+        pub struct Iter<'a, T> {
+            next: Option<&'a Node<T>>,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.next.map(|node| {
+                    self.next = node.next.as_deref();
+                    &node.value
+                })
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a PersistentList<T> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+    };
+}
+
+pub mod rc;
+pub mod sync;
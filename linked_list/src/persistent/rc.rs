@@ -0,0 +1,67 @@
+//! A [PersistentList] sharing its nodes through `Rc`
+
+use std::rc::Rc;
+
+persistent_list!(Rc);
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent::rc::PersistentList;
+    use std::rc::Rc;
+
+    fn strong_count<T>(list: &PersistentList<T>) -> usize {
+        list.head.as_ref().map_or(0, Rc::strong_count)
+    }
+
+    #[test]
+    fn prepending_shares_the_original() {
+        let shared: PersistentList<usize> = [1, 2].into_iter().collect();
+        let a = shared.prepend(10);
+        let b = shared.prepend(20);
+
+        assert_eq!(strong_count(&shared), 3);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![10, 1, 2]);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![20, 1, 2]);
+        assert_eq!(shared.len(), 2);
+    }
+
+    #[test]
+    fn the_tail_shares_the_rest_of_the_list() {
+        let list: PersistentList<usize> = (0..3).collect();
+        let tail = list.tail();
+
+        assert_eq!(tail.head(), Some(&1));
+        assert_eq!(strong_count(&tail), 2);
+        assert_eq!(list.tail().tail().tail(), PersistentList::create());
+        assert!(PersistentList::<usize>::create().tail().is_empty());
+    }
+
+    #[test]
+    fn dropping_a_list_releases_only_its_own_nodes() {
+        let shared: PersistentList<usize> = (0..3).collect();
+        let longer = shared.prepend(7);
+        assert_eq!(strong_count(&shared), 2);
+
+        drop(longer);
+        assert_eq!(strong_count(&shared), 1);
+        assert_eq!(shared.len(), 3);
+    }
+
+    #[test]
+    fn clones_share_the_head() {
+        let list = PersistentList::create().prepend("a");
+        let copy = list.clone();
+
+        assert_eq!(strong_count(&list), 2);
+        assert_eq!(format!("{:?}", copy), r#"["a"]"#);
+    }
+
+    #[test]
+    fn dropping_a_million_nodes_does_not_overflow_the_stack() {
+        let list: PersistentList<usize> = (0..1_000_000).collect();
+        let tail = list.tail();
+
+        drop(list);
+        assert_eq!(tail.head(), Some(&1));
+    }
+}
@@ -0,0 +1,68 @@
+//! A [PersistentList] sharing its nodes through `Arc`, so that it can cross threads
+
+use std::sync::Arc;
+
+persistent_list!(Arc);
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent::sync::PersistentList;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[test]
+    fn threads_extend_a_shared_list() {
+        let shared: PersistentList<usize> = (0..3).collect();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let list = shared.clone();
+                thread::spawn(move || list.prepend(i * 10))
+            })
+            .collect();
+        let lists: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(shared.head.as_ref().map(Arc::strong_count), Some(5));
+        for (i, list) in lists.iter().enumerate() {
+            assert_eq!(list.head(), Some(&(i * 10)));
+            assert_eq!(list.tail(), shared);
+        }
+
+        drop(lists);
+        assert_eq!(shared.head.as_ref().map(Arc::strong_count), Some(1));
+    }
+
+    #[test]
+    fn dropping_a_million_nodes_does_not_overflow_the_stack() {
+        let list: PersistentList<usize> = (0..1_000_000).collect();
+        drop(list);
+    }
+
+    #[test]
+    fn threads_dropping_lists_with_a_shared_tail_do_not_overflow_the_stack() {
+        for _ in 0..200 {
+            let shared: PersistentList<usize> = (0..20_000).collect();
+            let lists = [shared.prepend(1), shared.prepend(2)];
+            drop(shared);
+
+            let barrier = Arc::new(Barrier::new(2));
+            let handles: Vec<_> = lists
+                .into_iter()
+                .map(|list| {
+                    let barrier = Arc::clone(&barrier);
+                    // a recursive drop of the shared tail would not fit in this stack
+                    thread::Builder::new()
+                        .stack_size(64 * 1024)
+                        .spawn(move || {
+                            barrier.wait();
+                            drop(list);
+                        })
+                        .unwrap()
+                })
+                .collect();
+
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        }
+    }
+}
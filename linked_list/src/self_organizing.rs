@@ -0,0 +1,537 @@
+use std::borrow::Borrow;
+use std::fmt;
+
+/// Decides where a [SelfOrganizingList] moves an entry when it is looked up.
+///
+/// [MoveToFront], [Transpose] and [FrequencyCount] are the classic heuristics, and any
+/// other can be plugged in by implementing this trait.
+pub trait AccessStrategy {
+    /// The index that the entry found at `index` moves to, where larger values are clamped
+    /// to `index`
+    ///
+    /// `count` is the number of lookups of the entry, including this one, and `counts` yields
+    /// those of the entries in front of it, from the front.
+    fn target<K, V>(&mut self, index: usize, count: u64, counts: Counts<'_, K, V>) -> usize;
+}
+
+/// Moves the entry to the front, adapting quickly when the popular keys change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MoveToFront;
+
+impl AccessStrategy for MoveToFront {
+    fn target<K, V>(&mut self, _: usize, _: u64, _: Counts<'_, K, V>) -> usize {
+        0
+    }
+}
+
+/// Swaps the entry with the one before it, converging slowly but staying stable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Transpose;
+
+impl AccessStrategy for Transpose {
+    fn target<K, V>(&mut self, index: usize, _: u64, _: Counts<'_, K, V>) -> usize {
+        index.saturating_sub(1)
+    }
+}
+
+/// Keeps the entries ordered by how often they were looked up, most often first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrequencyCount;
+
+impl AccessStrategy for FrequencyCount {
+    fn target<K, V>(&mut self, index: usize, count: u64, mut counts: Counts<'_, K, V>) -> usize {
+        // the entries before it were looked up at least as often as it was before
+        counts.position(|other| other < count).unwrap_or(index)
+    }
+}
+
+/// How well the order of a [SelfOrganizingList] suits its lookups
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Statistics {
+    pub lookups: u64,
+    pub hits: u64,
+    /// The number of entries compared over all lookups, where a miss compares every entry
+    pub total_depth: u64,
+}
+
+impl Statistics {
+    pub fn misses(&self) -> u64 {
+        self.lookups - self.hits
+    }
+
+    /// The number of entries compared per lookup, or 0 before the first lookup
+    pub fn average_depth(&self) -> f64 {
+        if self.lookups == 0 {
+            0.0
+        } else {
+            self.total_depth as f64 / self.lookups as f64
+        }
+    }
+}
+
+type Link<K, V> = Option<Box<Entry<K, V>>>;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    // the number of lookups of this entry, which strategies such as `FrequencyCount` order by
+    count: u64,
+    next: Link<K, V>,
+}
+
+/// A map kept as a singly linked list, which reorders itself on every lookup so that
+/// frequently used keys end up near the front.
+///
+/// Lookups search from the front, so they take O(n) in the worst case, but for small maps
+/// with a few popular keys the search is short and cheaper than hashing. Lookups take
+/// `&mut self` because they reorder the list.
+pub struct SelfOrganizingList<K, V, S = MoveToFront> {
+    head: Link<K, V>,
+    len: usize,
+    strategy: S,
+    statistics: Statistics,
+}
+
+impl<K: Eq, V, S: AccessStrategy> SelfOrganizingList<K, V, S> {
+    pub fn create(strategy: S) -> SelfOrganizingList<K, V, S> {
+        SelfOrganizingList {
+            head: None,
+            len: 0,
+            strategy,
+            statistics: Statistics::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        self.statistics
+    }
+
+    pub fn reset_statistics(&mut self) {
+        self.statistics = Statistics::default();
+    }
+
+    /// Adds an entry at the back, or replaces the value of an existing key in place
+    ///
+    /// Inserting does not count as a lookup, so it neither reorders nor shows in the statistics.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut link = &mut self.head;
+        while let Some(entry) = link {
+            if entry.key == key {
+                return Some(std::mem::replace(&mut entry.value, value));
+            }
+            link = &mut entry.next;
+        }
+
+        *link = Some(Box::new(Entry {
+            key,
+            value,
+            count: 0,
+            next: None,
+        }));
+        self.len += 1;
+
+        None
+    }
+
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.look_up(key).map(|entry| &entry.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.look_up(key).map(|entry| &mut entry.value)
+    }
+
+    /// Removes an entry, which does not count as a lookup
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let (_, entry) = self.unlink(key);
+        entry.map(|entry| entry.value)
+    }
+
+    /// The entries in their current order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    /// Searches for a key, records the lookup and moves the entry to where the strategy
+    /// wants it
+    ///
+    /// The entry is unlinked as soon as it is found, so only the entries in front of its new
+    /// position are walked again.
+    fn look_up<Q>(&mut self, key: &Q) -> Option<&mut Entry<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let (index, found) = self.unlink(key);
+        self.statistics.lookups += 1;
+        self.statistics.total_depth += found.as_ref().map_or(index, |_| index + 1) as u64;
+        let mut entry = found?;
+        self.statistics.hits += 1;
+
+        entry.count += 1;
+        let counts = Counts {
+            next: self.head.as_deref(),
+            remaining: index,
+        };
+        let target = self.strategy.target(index, entry.count, counts).min(index);
+
+        let mut link = &mut self.head;
+        for _ in 0..target {
+            link = &mut link.as_mut().expect("target within the list").next;
+        }
+        entry.next = link.take();
+        self.len += 1;
+
+        Some(&mut **link.insert(entry))
+    }
+
+    /// Splices out the entry of a key in a single walk, returning the index it had, or the
+    /// length of the list when the key is missing
+    fn unlink<Q>(&mut self, key: &Q) -> (usize, Option<Box<Entry<K, V>>>)
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mut index = 0;
+        let mut link = &mut self.head;
+        while link.as_ref().is_some_and(|entry| entry.key.borrow() != key) {
+            link = &mut link.as_mut().unwrap().next;
+            index += 1;
+        }
+
+        let entry = link.take().map(|mut entry| {
+            *link = entry.next.take();
+            entry
+        });
+        if entry.is_some() {
+            self.len -= 1;
+        }
+
+        (index, entry)
+    }
+}
+
+impl<K, V, S> Drop for SelfOrganizingList<K, V, S> {
+    fn drop(&mut self) {
+        // unlinks the entries one by one instead of dropping them recursively
+        let mut rest = self.head.take();
+        while let Some(mut entry) = rest {
+            rest = entry.next.take();
+        }
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for SelfOrganizingList<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries = f.debug_map();
+        let mut current = self.head.as_deref();
+        while let Some(entry) = current {
+            entries.entry(&entry.key, &entry.value);
+            current = entry.next.as_deref();
+        }
+
+        entries.finish()
+    }
+}
+
+/// Iterates over the entries of a [SelfOrganizingList] without reordering them
+pub struct Iter<'a, K, V> {
+    next: Option<&'a Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|entry| {
+            self.next = entry.next.as_deref();
+            (&entry.key, &entry.value)
+        })
+    }
+}
+
+/// Yields the lookup counts of the entries in front of a looked up entry, see
+/// [AccessStrategy::target]
+pub struct Counts<'a, K, V> {
+    next: Option<&'a Entry<K, V>>,
+    remaining: usize,
+}
+
+impl<K, V> Iterator for Counts<'_, K, V> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.next.map(|entry| {
+            self.next = entry.next.as_deref();
+            self.remaining -= 1;
+            entry.count
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Counts<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::self_organizing::{
+        AccessStrategy, Counts, FrequencyCount, MoveToFront, SelfOrganizingList, Statistics,
+        Transpose,
+    };
+
+    fn with_keys<S: AccessStrategy>(
+        strategy: S,
+        keys: &[&'static str],
+    ) -> SelfOrganizingList<&'static str, usize, S> {
+        let mut list = SelfOrganizingList::create(strategy);
+        for (value, key) in keys.iter().enumerate() {
+            list.insert(*key, value);
+        }
+
+        list
+    }
+
+    fn keys<S: AccessStrategy>(
+        list: &SelfOrganizingList<&'static str, usize, S>,
+    ) -> Vec<&'static str> {
+        list.iter().map(|(k, _)| *k).collect()
+    }
+
+    #[test]
+    fn moving_to_the_front() {
+        let mut list = with_keys(MoveToFront, &["a", "b", "c", "d"]);
+
+        assert_eq!(list.get("c"), Some(&2));
+        assert_eq!(keys(&list), vec!["c", "a", "b", "d"]);
+    }
+
+    #[test]
+    fn transposing_with_the_previous_entry() {
+        let mut list = with_keys(Transpose, &["a", "b", "c", "d"]);
+
+        assert_eq!(list.get("c"), Some(&2));
+        assert_eq!(keys(&list), vec!["a", "c", "b", "d"]);
+        list.get("a");
+        assert_eq!(keys(&list), vec!["a", "c", "b", "d"]);
+    }
+
+    #[test]
+    fn ordering_by_frequency() {
+        let mut list = with_keys(FrequencyCount, &["a", "b", "c", "d"]);
+        list.get("d");
+        list.get("d");
+        list.get("b");
+
+        assert_eq!(keys(&list), vec!["d", "b", "a", "c"]);
+
+        // ties keep the entry that reached the count first in front
+        list.get("b");
+        assert_eq!(keys(&list), vec!["d", "b", "a", "c"]);
+        list.get("c");
+        assert_eq!(keys(&list), vec!["d", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn statistics_count_the_entries_compared() {
+        let mut list = with_keys(MoveToFront, &["a", "b", "c"]);
+        list.get("c");
+        list.get("c");
+        list.get("x");
+
+        let expected = Statistics {
+            lookups: 3,
+            hits: 2,
+            total_depth: 3 + 1 + 3,
+        };
+        assert_eq!(list.statistics(), expected);
+        assert_eq!(list.statistics().misses(), 1);
+        assert!((list.statistics().average_depth() - 7.0 / 3.0).abs() < 1e-9);
+
+        list.reset_statistics();
+        assert_eq!(list.statistics().average_depth(), 0.0);
+    }
+
+    #[test]
+    fn inserting_an_existing_key_replaces_its_value() {
+        let mut list = with_keys(Transpose, &["a", "b"]);
+
+        assert_eq!(list.insert("b", 10), Some(1));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.statistics().lookups, 0);
+        assert_eq!(format!("{:?}", list), r#"{"a": 0, "b": 10}"#);
+    }
+
+    #[test]
+    fn changing_and_removing_values() {
+        let mut list = with_keys(FrequencyCount, &["a", "b"]);
+        *list.get_mut("b").unwrap() += 5;
+
+        assert_eq!(list.remove("b"), Some(6));
+        assert_eq!(list.remove("b"), None);
+        assert_eq!(keys(&list), vec!["a"]);
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn keys_can_be_looked_up_by_a_borrowed_form() {
+        let mut list = SelfOrganizingList::create(MoveToFront);
+        list.insert(String::from("key"), 1);
+
+        assert_eq!(list.get("key"), Some(&1));
+    }
+
+    /// A fixed xorshift generator, so that the tests see the same values on every run
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    /// Samples keys with a probability proportional to 1 / rank
+    fn zipfian_keys(keys: usize, samples: usize) -> Vec<usize> {
+        let weights: Vec<f64> = (1..=keys).map(|rank| 1.0 / rank as f64).collect();
+        let total: f64 = weights.iter().sum();
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+        (0..samples)
+            .map(|_| {
+                let random = xorshift(&mut state);
+                let mut target = (random >> 11) as f64 / (1u64 << 53) as f64 * total;
+                weights
+                    .iter()
+                    .position(|weight| {
+                        target -= weight;
+                        target < 0.0
+                    })
+                    .unwrap_or(keys - 1)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn strategies_can_be_plugged_in() {
+        /// Moves entries halfway to the front, clamped to the index when asked for too much
+        struct Halfway;
+
+        impl AccessStrategy for Halfway {
+            fn target<K, V>(&mut self, index: usize, _: u64, counts: Counts<'_, K, V>) -> usize {
+                assert_eq!(counts.len(), index);
+                index / 2
+            }
+        }
+
+        let mut list = with_keys(Halfway, &["a", "b", "c", "d", "e"]);
+        list.get("e");
+
+        assert_eq!(keys(&list), vec!["a", "b", "e", "c", "d"]);
+        assert_eq!(list.len(), 5);
+    }
+
+    /// The keys in a shuffled order, so that their popularity does not follow their position
+    fn shuffled_keys(keys: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..keys).collect();
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        for i in (1..keys).rev() {
+            order.swap(i, xorshift(&mut state) as usize % (i + 1));
+        }
+
+        order
+    }
+
+    /// Inserts the keys in order and looks them up, returning the average number of entries
+    /// compared once the warm-up lookups have given the strategy time to reorder the list
+    fn average_depth<S: AccessStrategy>(
+        strategy: S,
+        order: &[usize],
+        warm_up: &[usize],
+        lookups: &[usize],
+    ) -> f64 {
+        let mut list = SelfOrganizingList::create(strategy);
+        for key in order {
+            list.insert(*key, ());
+        }
+        for key in warm_up {
+            list.get(key);
+        }
+        list.reset_statistics();
+        for key in lookups {
+            list.get(key);
+        }
+        assert_eq!(list.statistics().misses(), 0);
+
+        list.statistics().average_depth()
+    }
+
+    #[test]
+    fn the_search_depth_drops_under_a_zipfian_access_pattern() {
+        const KEYS: usize = 50;
+        let order = shuffled_keys(KEYS);
+        let keys = zipfian_keys(KEYS, 20_000);
+        let (warm_up, lookups) = keys.split_at(2_000);
+
+        // a plain list keeps every key at its position in the shuffled order
+        let mut depths = [0; KEYS];
+        for (position, key) in order.iter().enumerate() {
+            depths[*key] = position + 1;
+        }
+        let without_reordering =
+            lookups.iter().map(|key| depths[*key]).sum::<usize>() as f64 / lookups.len() as f64;
+
+        for (strategy, depth) in [
+            (
+                "MoveToFront",
+                average_depth(MoveToFront, &order, warm_up, lookups),
+            ),
+            (
+                "Transpose",
+                average_depth(Transpose, &order, warm_up, lookups),
+            ),
+            (
+                "FrequencyCount",
+                average_depth(FrequencyCount, &order, warm_up, lookups),
+            ),
+        ] {
+            assert!(
+                depth < without_reordering * 0.6,
+                "{} searched {} entries deep on average, against {} without reordering",
+                strategy,
+                depth,
+                without_reordering
+            );
+        }
+    }
+}
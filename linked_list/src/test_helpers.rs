@@ -0,0 +1,10 @@
+//! Helpers shared by the tests of the lists
+
+/// The values from front to back
+pub fn values<'a, T, L>(list: &'a L) -> Vec<T>
+where
+    T: Clone + 'a,
+    &'a L: IntoIterator<Item = &'a T>,
+{
+    list.into_iter().cloned().collect()
+}
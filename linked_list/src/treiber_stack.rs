@@ -0,0 +1,238 @@
+mod hazard;
+
+use crate::treiber_stack::hazard::Hazards;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicPtr, AtomicUsize, Ordering};
+
+/// The number of retired nodes at which a thread tries to free them
+const RECLAIM_THRESHOLD: usize = 64;
+
+struct Node<T> {
+    // moved out by the thread that pops the node, so never dropped along with the node
+    value: ManuallyDrop<T>,
+    // written once before the node is pushed, read only afterwards
+    next: *mut Node<T>,
+    // links the node into the list of retired nodes once it has been popped
+    retired_next: *mut Node<T>,
+}
+
+/// A lock-free LIFO stack that can be shared between threads.
+///
+/// Pushes and pops swap the head of a singly linked list with a compare-and-swap, as in
+/// Treiber's stack. A popping thread reads the `next` pointer of the head before swapping
+/// it out, while another thread may pop and free that head at the same time. Hazard
+/// pointers prevent this: a thread announces the node it is about to read, and popped
+/// nodes are only freed once no thread announces them.
+///
+/// This also avoids the ABA problem, where a head is popped, freed and its memory reused
+/// for a new head between the read and the swap of another thread, which would then
+/// succeed with a stale `next` pointer. A node announced by a thread cannot be freed, so
+/// its address cannot come back as a new head.
+pub struct TreiberStack<T> {
+    head: AtomicPtr<Node<T>>,
+    hazards: Hazards,
+    // popped nodes that may still be read by other threads
+    retired: AtomicPtr<Node<T>>,
+    retired_len: AtomicUsize,
+    marker: PhantomData<*mut T>,
+}
+
+// values move between threads through the stack, but are never shared between them
+unsafe impl<T: Send> Send for TreiberStack<T> {}
+unsafe impl<T: Send> Sync for TreiberStack<T> {}
+
+impl<T> TreiberStack<T> {
+    pub fn create() -> TreiberStack<T> {
+        TreiberStack {
+            head: AtomicPtr::new(ptr::null_mut()),
+            hazards: Hazards::create(),
+            retired: AtomicPtr::new(ptr::null_mut()),
+            retired_len: AtomicUsize::new(0),
+            marker: PhantomData,
+        }
+    }
+
+    /// Whether the stack was empty at the time of the call
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    pub fn push(&self, x: T) {
+        let node = Box::into_raw(Box::new(Node {
+            value: ManuallyDrop::new(x),
+            next: ptr::null_mut(),
+            retired_next: ptr::null_mut(),
+        }));
+
+        loop {
+            let head = self.head.load(Ordering::Relaxed);
+            unsafe { (*node).next = head };
+            if self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.hazards.acquire();
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            if head.is_null() {
+                return None;
+            }
+
+            // the head may have been popped and freed before it was protected, in which
+            // case it is no longer the head
+            guard.protect(head);
+            if self.head.load(Ordering::SeqCst) != head {
+                continue;
+            }
+
+            let next = unsafe { (*head).next };
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                drop(guard);
+                // only the thread that swapped the head out gets to move its value
+                let value = unsafe { ManuallyDrop::take(&mut (*head).value) };
+                self.retire(head);
+
+                return Some(value);
+            }
+        }
+    }
+
+    /// Hands a popped node over to be freed once no thread protects it
+    fn retire(&self, node: *mut Node<T>) {
+        // counted before it is pushed, as another thread may free it right after
+        let retired_len = self.retired_len.fetch_add(1, Ordering::Relaxed) + 1;
+        self.push_retired(node);
+        if retired_len >= RECLAIM_THRESHOLD {
+            self.reclaim();
+        }
+    }
+
+    fn push_retired(&self, node: *mut Node<T>) {
+        loop {
+            let head = self.retired.load(Ordering::Relaxed);
+            unsafe { (*node).retired_next = head };
+            if self
+                .retired
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+
+    /// Frees the retired nodes that no thread protects, retiring the others again
+    fn reclaim(&self) {
+        let mut node = self.retired.swap(ptr::null_mut(), Ordering::AcqRel);
+        // orders the removal of the nodes from the stack before reading the hazards, so a
+        // thread that protects a node after this has to find it removed
+        fence(Ordering::SeqCst);
+        let protected = self.hazards.protected();
+
+        while !node.is_null() {
+            let next = unsafe { (*node).retired_next };
+            if protected.contains(&node.cast()) {
+                self.push_retired(node);
+            } else {
+                drop(unsafe { Box::from_raw(node) });
+                self.retired_len.fetch_sub(1, Ordering::Relaxed);
+            }
+            node = next;
+        }
+    }
+}
+
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        let mut node = *self.head.get_mut();
+        while !node.is_null() {
+            let mut boxed = unsafe { Box::from_raw(node) };
+            unsafe { ManuallyDrop::drop(&mut boxed.value) };
+            node = boxed.next;
+        }
+
+        // the values of retired nodes have been moved out already
+        let mut node = *self.retired.get_mut();
+        while !node.is_null() {
+            let boxed = unsafe { Box::from_raw(node) };
+            node = boxed.retired_next;
+        }
+    }
+}
+
+impl<T> Default for TreiberStack<T> {
+    fn default() -> Self {
+        TreiberStack::create()
+    }
+}
+
+impl<T> fmt::Debug for TreiberStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TreiberStack")
+            .field("is_empty", &self.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::treiber_stack::{TreiberStack, RECLAIM_THRESHOLD};
+    use std::rc::Rc;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn values_come_out_in_reverse_order() {
+        let stack = TreiberStack::create();
+        for x in 0..3 {
+            stack.push(x);
+        }
+
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), Some(0));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn popped_nodes_are_freed_in_batches() {
+        let stack = TreiberStack::create();
+        for x in 0..RECLAIM_THRESHOLD + 1 {
+            stack.push(x);
+        }
+        for _ in 0..RECLAIM_THRESHOLD + 1 {
+            stack.pop();
+        }
+
+        assert_eq!(stack.retired_len.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn dropping_the_stack_drops_the_remaining_values() {
+        // the values are only moved within this thread, so an `Rc` can count them
+        let value = Rc::new(0);
+        let stack = TreiberStack::create();
+        for _ in 0..3 {
+            stack.push(Rc::clone(&value));
+        }
+        drop(stack.pop());
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(stack);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,136 @@
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+/// A slot in which a thread announces the node it is about to read, so that the node is
+/// not freed in the meantime
+pub(in crate::treiber_stack) struct HazardRecord {
+    pointer: AtomicPtr<()>,
+    active: AtomicBool,
+    // written once before the record is published, read only afterwards
+    next: *mut HazardRecord,
+}
+
+/// The hazard records of a stack, linked in a list that only grows.
+///
+/// A thread takes a free record for the duration of an operation, so there are about as
+/// many records as threads that have operated on the stack at the same time. Records are
+/// only freed along with the stack.
+pub(in crate::treiber_stack) struct Hazards {
+    records: AtomicPtr<HazardRecord>,
+}
+
+/// A hazard record taken by one thread, which is given back when the guard is dropped
+pub(in crate::treiber_stack) struct Guard<'a> {
+    record: &'a HazardRecord,
+}
+
+impl Hazards {
+    pub(in crate::treiber_stack) fn create() -> Hazards {
+        Hazards {
+            records: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Takes a free record, adding a new one when all records are in use
+    pub(in crate::treiber_stack) fn acquire(&self) -> Guard<'_> {
+        let mut ptr = self.records.load(Ordering::Acquire);
+        while !ptr.is_null() {
+            let record = unsafe { &*ptr };
+            if !record.active.load(Ordering::Relaxed)
+                && record
+                    .active
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return Guard { record };
+            }
+            ptr = record.next;
+        }
+
+        let ptr = Box::into_raw(Box::new(HazardRecord {
+            pointer: AtomicPtr::new(ptr::null_mut()),
+            active: AtomicBool::new(true),
+            next: ptr::null_mut(),
+        }));
+        loop {
+            let head = self.records.load(Ordering::Acquire);
+            unsafe { (*ptr).next = head };
+            if self
+                .records
+                .compare_exchange(head, ptr, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Guard {
+                    record: unsafe { &*ptr },
+                };
+            }
+        }
+    }
+
+    /// Every pointer that is currently protected by a thread
+    pub(in crate::treiber_stack) fn protected(&self) -> Vec<*mut ()> {
+        let mut protected = vec![];
+        let mut ptr = self.records.load(Ordering::Acquire);
+        while !ptr.is_null() {
+            let record = unsafe { &*ptr };
+            let pointer = record.pointer.load(Ordering::SeqCst);
+            if !pointer.is_null() {
+                protected.push(pointer);
+            }
+            ptr = record.next;
+        }
+
+        protected
+    }
+}
+
+impl Drop for Hazards {
+    fn drop(&mut self) {
+        let mut ptr = *self.records.get_mut();
+        while !ptr.is_null() {
+            let record = unsafe { Box::from_raw(ptr) };
+            ptr = record.next;
+        }
+    }
+}
+
+impl Guard<'_> {
+    /// Announces that the node behind `ptr` is about to be read.
+    ///
+    /// The node may have been removed before the announcement was visible, so callers
+    /// must check that it is still reachable before reading it.
+    pub(in crate::treiber_stack) fn protect<T>(&self, ptr: *mut T) {
+        self.record.pointer.store(ptr.cast(), Ordering::SeqCst);
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.record
+            .pointer
+            .store(ptr::null_mut(), Ordering::Release);
+        self.record.active.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::treiber_stack::hazard::Hazards;
+
+    #[test]
+    fn records_are_reused_once_released() {
+        let hazards = Hazards::create();
+        let mut value: usize = 5;
+        let first = hazards.acquire();
+        first.protect(&mut value as *mut usize);
+        let second = hazards.acquire();
+
+        assert_eq!(hazards.protected().len(), 1);
+        assert!(!std::ptr::eq(first.record, second.record));
+
+        let record = first.record as *const _;
+        drop(first);
+        assert!(hazards.protected().is_empty());
+        assert!(std::ptr::eq(hazards.acquire().record, record));
+    }
+}
@@ -0,0 +1,79 @@
+use linked_list::TreiberStack;
+use std::sync::Arc;
+use std::thread;
+
+#[cfg(not(miri))]
+const THREADS: usize = 8;
+#[cfg(miri)]
+const THREADS: usize = 3;
+
+#[cfg(not(miri))]
+const VALUES_PER_THREAD: usize = 20_000;
+#[cfg(miri)]
+const VALUES_PER_THREAD: usize = 100;
+
+#[test]
+fn every_value_is_popped_exactly_once() {
+    let stack = Arc::new(TreiberStack::create());
+
+    // every thread pushes its own values and pops whatever is on top, so nodes are freed
+    // and their memory reused while other threads are popping
+    let threads: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || {
+                let mut popped = vec![];
+                for i in 0..VALUES_PER_THREAD {
+                    stack.push(Box::new(t * VALUES_PER_THREAD + i));
+                    if i % 2 == 1 {
+                        popped.extend(stack.pop());
+                        popped.extend(stack.pop());
+                    }
+                }
+                popped
+            })
+        })
+        .collect();
+
+    let mut popped: Vec<Box<usize>> = threads
+        .into_iter()
+        .flat_map(|t| t.join().unwrap())
+        .collect();
+    while let Some(value) = stack.pop() {
+        popped.push(value);
+    }
+
+    let mut seen = vec![false; THREADS * VALUES_PER_THREAD];
+    for value in popped {
+        assert!(!seen[*value], "{} was popped twice", value);
+        seen[*value] = true;
+    }
+    assert!(seen.iter().all(|popped| *popped));
+}
+
+#[test]
+fn values_left_on_the_stack_are_dropped_with_it() {
+    let value = Arc::new(());
+    let stack = Arc::new(TreiberStack::create());
+
+    let threads: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let stack = Arc::clone(&stack);
+            let value = Arc::clone(&value);
+            thread::spawn(move || {
+                for i in 0..VALUES_PER_THREAD {
+                    stack.push(Arc::clone(&value));
+                    if i % 3 == 0 {
+                        stack.pop();
+                    }
+                }
+            })
+        })
+        .collect();
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    drop(stack);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
@@ -0,0 +1,157 @@
+use std::alloc::Layout;
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+/// The number of buckets needed to address every `usize` index: bucket `b` holds `2^b` slots.
+const BUCKETS: usize = usize::BITS as usize;
+
+/// ConcurrentVector
+///
+/// An append-only vector that can be shared between threads without a lock.
+///
+/// Elements are stored in buckets of doubling size (1, 2, 4, 8, ...). A bucket is
+/// allocated once and never moved or resized, so a reference to an element stays
+/// valid for as long as the vector lives, even while other threads keep pushing.
+pub struct ConcurrentVector<T> {
+    buckets: [AtomicPtr<Slot<T>>; BUCKETS],
+    reserved: AtomicUsize,
+    len: AtomicUsize,
+}
+
+struct Slot<T> {
+    ready: AtomicBool,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// safety: values are only written once, by the thread that reserved the slot, before the slot
+// is published as ready. Afterwards they are only ever shared by reference.
+unsafe impl<T: Send> Send for ConcurrentVector<T> {}
+unsafe impl<T: Send + Sync> Sync for ConcurrentVector<T> {}
+
+impl<T> ConcurrentVector<T> {
+    pub fn new() -> Self {
+        ConcurrentVector {
+            buckets: std::array::from_fn(|_| AtomicPtr::new(std::ptr::null_mut())),
+            reserved: AtomicUsize::new(0),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// A snapshot of the number of elements that have been completely pushed.
+    ///
+    /// Pushes running concurrently may complete out of order, so an index below `len`
+    /// can still be in flight for a short while. `get` returns `None` for such an index.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends an element and returns the index at which it was stored.
+    pub fn push(&self, element: T) -> usize {
+        let index = self.reserved.fetch_add(1, Ordering::Relaxed);
+        let (bucket, offset) = Self::locate(index)
+            .expect("ConcurrentVector cannot hold more than usize::MAX elements");
+        let slots = self.bucket_or_allocate(bucket);
+
+        unsafe {
+            let slot = &*slots.add(offset);
+            (*slot.value.get()).write(element);
+            slot.ready.store(true, Ordering::Release);
+        }
+        self.len.fetch_add(1, Ordering::Release);
+
+        index
+    }
+
+    /// Wait-free lookup of an element, returning `None` while it has not been fully pushed.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (bucket, offset) = Self::locate(index)?;
+        let slots = self.buckets[bucket].load(Ordering::Acquire);
+        if slots.is_null() {
+            return None;
+        }
+
+        unsafe {
+            let slot = &*slots.add(offset);
+            if slot.ready.load(Ordering::Acquire) {
+                Some((*slot.value.get()).assume_init_ref())
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Translates an index to the bucket holding it and the offset within that bucket,
+    /// or `None` for `usize::MAX`, which no bucket can hold.
+    fn locate(index: usize) -> Option<(usize, usize)> {
+        // shifting by one makes bucket `b` cover the positions [2^b, 2^(b+1))
+        let position = index.checked_add(1)?;
+        let bucket = (usize::BITS - 1 - position.leading_zeros()) as usize;
+
+        Some((bucket, position - (1 << bucket)))
+    }
+
+    fn bucket_layout(bucket: usize) -> Layout {
+        Layout::array::<Slot<T>>(1 << bucket)
+            .expect("Failed to allocate memory due to memory layout error")
+    }
+
+    fn bucket_or_allocate(&self, bucket: usize) -> *mut Slot<T> {
+        let current = self.buckets[bucket].load(Ordering::Acquire);
+        if !current.is_null() {
+            return current;
+        }
+
+        // zeroed memory marks every slot as not ready
+        let layout = Self::bucket_layout(bucket);
+        let allocated = unsafe { std::alloc::alloc_zeroed(layout) as *mut Slot<T> };
+        if allocated.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
+
+        match self.buckets[bucket].compare_exchange(
+            std::ptr::null_mut(),
+            allocated,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => allocated,
+            Err(winner) => {
+                // another thread allocated the bucket first, so ours is not needed
+                unsafe { std::alloc::dealloc(allocated as *mut u8, layout) };
+                winner
+            }
+        }
+    }
+}
+
+impl<T> Default for ConcurrentVector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ConcurrentVector<T> {
+    fn drop(&mut self) {
+        for (bucket, slots) in self.buckets.iter_mut().enumerate() {
+            let slots = *slots.get_mut();
+            if slots.is_null() {
+                continue;
+            }
+
+            unsafe {
+                for offset in 0..(1 << bucket) {
+                    let slot = &mut *slots.add(offset);
+                    if *slot.ready.get_mut() {
+                        slot.value.get_mut().assume_init_drop();
+                    }
+                }
+                std::alloc::dealloc(slots as *mut u8, Self::bucket_layout(bucket));
+            }
+        }
+    }
+}
@@ -1 +1,3 @@
+pub mod concurrent_vector;
 pub mod my_vector;
+pub mod sparse_vec;
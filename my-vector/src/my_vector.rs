@@ -1,13 +1,15 @@
+pub mod persistence;
+
 /// MyVector
 ///
 /// Implements a dynamic array in Rust as a study exercise.
-pub struct MyVector {
-    data_ptr: *mut usize,
+pub struct MyVector<T = usize> {
+    data_ptr: *mut T,
     len: usize,
     capacity: usize
 }
 
-impl MyVector {
+impl<T> MyVector<T> {
     pub fn new() -> Self {
         MyVector {
             data_ptr: std::ptr::null_mut(),
@@ -16,26 +18,48 @@ impl MyVector {
         }
     }
 
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut v = MyVector::new();
+        v.reserve(capacity);
+
+        v
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn has(&self, index: usize) -> bool {
         index < self.len
     }
 
-    pub fn get(&self, index: usize) -> &usize {
-        unsafe {
-            let ptr = self.data_ptr.add(index) as *const usize;
-            let data = &*ptr;
+    pub fn get(&self, index: usize) -> &T {
+        unsafe { &*self.data_ptr.add(index) }
+    }
 
-            data
+    pub fn get_mut(&mut self, index: usize) -> &mut T {
+        unsafe { &mut *self.data_ptr.add(index) }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if self.data_ptr.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.data_ptr, self.len) }
         }
     }
 
-    pub fn find<F>(&self, predicate: F) -> Option<&usize>
+    pub fn find<F>(&self, predicate: F) -> Option<&T>
     where
-        F: Fn(&usize) -> bool
+        F: Fn(&T) -> bool
     {
         for n in 0..self.len {
             let v = self.get(n);
@@ -47,16 +71,27 @@ impl MyVector {
         None
     }
 
+    /// Makes sure at least `additional` more elements fit without resizing
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("Capacity overflow");
+        if required > self.capacity {
+            // growing at least twofold, like adding does, keeps repeated small reserves amortized O(1)
+            self.resize(std::cmp::max(required, self.capacity * 2));
+        }
+    }
+
     fn resize(&mut self, new_capacity: usize) {
+        assert!(std::mem::size_of::<T>() > 0, "MyVector does not support zero sized types");
+
         self.data_ptr = unsafe {
            let new_data_ptr = std::alloc::alloc(
-                std::alloc::Layout::array::<usize>(new_capacity).expect("Failed to allocate memory due to memory layout error")
-            ) as *mut usize;
+                std::alloc::Layout::array::<T>(new_capacity).expect("Failed to allocate memory due to memory layout error")
+            ) as *mut T;
             if !self.data_ptr.is_null() {
                 std::ptr::copy_nonoverlapping(self.data_ptr, new_data_ptr, self.len);
                 std::alloc::dealloc(
                     self.data_ptr as *mut u8,
-                    std::alloc::Layout::array::<usize>(self.capacity).expect("Failed to deallocate memory due to memory layout error")
+                    std::alloc::Layout::array::<T>(self.capacity).expect("Failed to deallocate memory due to memory layout error")
                 )
             } else {
                 // current data ptr is null, so we are creating a new vector with no old data to move and deallocate
@@ -64,15 +99,14 @@ impl MyVector {
 
             new_data_ptr
         };
-        self.capacity += 1;
+        self.capacity = new_capacity;
     }
 
-    pub fn add(&mut self, element: usize) {
+    pub fn add(&mut self, element: T) {
 
-        // resize if necessary
+        // resize if necessary, doubling the capacity to keep adding amortized O(1)
         if self.len >= self.capacity {
-            // todo: make a smarter strategy to resize the vector
-            self.resize(self.capacity + 1);
+            self.resize(std::cmp::max(1, self.capacity * 2));
         }
 
         // appends the element to the end fo the vector
@@ -88,11 +122,31 @@ impl MyVector {
         self.len += 1;
     }
 
+    pub fn insert(&mut self, index: usize, element: T) {
+        assert!(index <= self.len, "Cannot insert beyond the end of the vector");
+
+        if self.len >= self.capacity {
+            self.resize(std::cmp::max(1, self.capacity * 2));
+        }
+
+        unsafe {
+            // shift the elements from the index onwards one position to the right
+            let location = self.data_ptr.add(index);
+            std::ptr::copy(location, location.add(1), self.len - index);
+            std::ptr::write(location, element);
+        }
+
+        self.len += 1;
+    }
+
     pub fn drop(&mut self, index: usize) {
         unsafe {
             // what is the ptr of the element to be removed?
             let to_be_removed = self.data_ptr.add(index);
 
+            // the removed element may own resources of its own
+            std::ptr::drop_in_place(to_be_removed);
+
             // copy the remaining elements in that location
             std::ptr::copy(to_be_removed.add(1), to_be_removed, self.len - index - 1);
 
@@ -105,13 +159,33 @@ impl MyVector {
     }
 }
 
-pub struct MyVectorIteratorState<'a> {
-    vec: &'a MyVector,
+impl<T> Default for MyVector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for MyVector<T> {
+    fn drop(&mut self) {
+        if !self.data_ptr.is_null() {
+            unsafe {
+                std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(self.data_ptr, self.len));
+                std::alloc::dealloc(
+                    self.data_ptr as *mut u8,
+                    std::alloc::Layout::array::<T>(self.capacity).expect("Failed to deallocate memory due to memory layout error")
+                )
+            }
+        }
+    }
+}
+
+pub struct MyVectorIteratorState<'a, T> {
+    vec: &'a MyVector<T>,
     current: usize
 }
 
-impl <'a> Iterator for MyVectorIteratorState<'a> {
-    type Item = &'a usize;
+impl <'a, T> Iterator for MyVectorIteratorState<'a, T> {
+    type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.vec.has(self.current) {
@@ -124,9 +198,9 @@ impl <'a> Iterator for MyVectorIteratorState<'a> {
     }
 }
 
-impl <'a> IntoIterator for &'a MyVector {
-    type Item = &'a usize;
-    type IntoIter = MyVectorIteratorState<'a>;
+impl <'a, T> IntoIterator for &'a MyVector<T> {
+    type Item = &'a T;
+    type IntoIter = MyVectorIteratorState<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
         MyVectorIteratorState {
@@ -148,4 +222,4 @@ macro_rules! myvec {
         v
         }
     };
-}
\ No newline at end of file
+}
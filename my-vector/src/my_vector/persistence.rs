@@ -0,0 +1,365 @@
+//! Binary persistence for vectors of primitive integers.
+//!
+//! The format is a 16 byte header, the raw payload and a trailing checksum:
+//!
+//! | offset | size | field                                        |
+//! |--------|------|----------------------------------------------|
+//! | 0      | 4    | magic bytes `MYVC`                           |
+//! | 4      | 1    | format version                               |
+//! | 5      | 1    | element width in bytes                       |
+//! | 6      | 1    | payload endianness (0 = little, 1 = big)     |
+//! | 7      | 1    | reserved, always 0                           |
+//! | 8      | 8    | number of elements (little endian `u64`)     |
+//! | 16     | n*w  | elements in the endianness of the header     |
+//! | 16+n*w | 8    | FNV-1a hash of the payload (little endian)   |
+//!
+//! The payload is written in the native endianness of the writing machine, so
+//! it starts at a 16 byte offset and can be reinterpreted in place by [view].
+
+use crate::my_vector::MyVector;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+
+pub const MAGIC: [u8; 4] = *b"MYVC";
+pub const VERSION: u8 = 1;
+pub const HEADER_LEN: usize = 16;
+pub const CHECKSUM_LEN: usize = 8;
+
+const LITTLE_ENDIAN: u8 = 0;
+const BIG_ENDIAN: u8 = 1;
+const NATIVE_ENDIAN: u8 = if cfg!(target_endian = "big") {
+    BIG_ENDIAN
+} else {
+    LITTLE_ENDIAN
+};
+
+/// The number of bytes read from or written to a stream at once
+const CHUNK_LEN: usize = 8 * 1024;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Element types for which every bit pattern is a valid value, so they can be
+/// written and read back as raw bytes.
+pub trait PlainData: sealed::Sealed + Copy {
+    const WIDTH: u8;
+
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! plain_data {
+    ($($t: ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl PlainData for $t {
+                const WIDTH: u8 = std::mem::size_of::<$t>() as u8;
+
+                fn swap_bytes(self) -> Self {
+                    <$t>::swap_bytes(self)
+                }
+            }
+        )*
+    };
+}
+
+plain_data!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug)]
+pub enum PersistenceError {
+    Io(std::io::Error),
+    InvalidMagic {
+        found: [u8; 4],
+    },
+    UnsupportedVersion {
+        found: u8,
+    },
+    ElementWidthMismatch {
+        expected: u8,
+        found: u8,
+    },
+    InvalidEndianness {
+        found: u8,
+    },
+    /// The reserved header byte was not 0, so the data was written by another format
+    InvalidReservedByte {
+        found: u8,
+    },
+    /// The payload was written on a machine with a different endianness, so it
+    /// cannot be reinterpreted without copying
+    ForeignEndianness,
+    LengthOverflow {
+        length: u64,
+    },
+    Truncated {
+        expected: usize,
+        found: usize,
+    },
+    Misaligned {
+        alignment: usize,
+    },
+    ChecksumMismatch {
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl Display for PersistenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistenceError::Io(e) => write!(f, "I/O error: {}", e),
+            PersistenceError::InvalidMagic { found } => {
+                write!(f, "Invalid magic bytes {:?}", found)
+            }
+            PersistenceError::UnsupportedVersion { found } => {
+                write!(f, "Unsupported format version {}", found)
+            }
+            PersistenceError::ElementWidthMismatch { expected, found } => write!(
+                f,
+                "Expected elements of {} bytes, found {} bytes",
+                expected, found
+            ),
+            PersistenceError::InvalidEndianness { found } => {
+                write!(f, "Invalid endianness marker {}", found)
+            }
+            PersistenceError::InvalidReservedByte { found } => {
+                write!(f, "Reserved header byte is {} instead of 0", found)
+            }
+            PersistenceError::ForeignEndianness => {
+                write!(f, "Payload endianness differs from this machine")
+            }
+            PersistenceError::LengthOverflow { length } => {
+                write!(f, "A length of {} elements does not fit in memory", length)
+            }
+            PersistenceError::Truncated { expected, found } => {
+                write!(f, "Expected {} bytes, found {} bytes", expected, found)
+            }
+            PersistenceError::Misaligned { alignment } => {
+                write!(f, "Payload is not aligned to {} bytes", alignment)
+            }
+            PersistenceError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "Checksum mismatch, expected {:#x}, found {:#x}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PersistenceError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PersistenceError {
+    fn from(e: std::io::Error) -> Self {
+        PersistenceError::Io(e)
+    }
+}
+
+struct Header {
+    width: u8,
+    endianness: u8,
+    length: u64,
+}
+
+impl Header {
+    fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0; HEADER_LEN];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4] = VERSION;
+        bytes[5] = self.width;
+        bytes[6] = self.endianness;
+        bytes[8..16].copy_from_slice(&self.length.to_le_bytes());
+
+        bytes
+    }
+
+    fn decode<T: PlainData>(bytes: &[u8; HEADER_LEN]) -> Result<Header, PersistenceError> {
+        let magic: [u8; 4] = bytes[0..4].try_into().unwrap();
+        if magic != MAGIC {
+            return Err(PersistenceError::InvalidMagic { found: magic });
+        }
+        if bytes[4] != VERSION {
+            return Err(PersistenceError::UnsupportedVersion { found: bytes[4] });
+        }
+        if bytes[5] != T::WIDTH {
+            return Err(PersistenceError::ElementWidthMismatch {
+                expected: T::WIDTH,
+                found: bytes[5],
+            });
+        }
+        if bytes[6] != LITTLE_ENDIAN && bytes[6] != BIG_ENDIAN {
+            return Err(PersistenceError::InvalidEndianness { found: bytes[6] });
+        }
+        if bytes[7] != 0 {
+            return Err(PersistenceError::InvalidReservedByte { found: bytes[7] });
+        }
+
+        Ok(Header {
+            width: bytes[5],
+            endianness: bytes[6],
+            length: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+        })
+    }
+
+    /// The payload size in bytes, if it is addressable on this machine
+    fn payload_len(&self) -> Result<usize, PersistenceError> {
+        usize::try_from(self.length)
+            .ok()
+            .and_then(|length| length.checked_mul(self.width as usize))
+            .ok_or(PersistenceError::LengthOverflow {
+                length: self.length,
+            })
+    }
+}
+
+/// 64 bit FNV-1a, which can be computed incrementally over chunks
+struct Checksum(u64);
+
+impl Checksum {
+    fn new() -> Checksum {
+        Checksum(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn as_bytes<T: PlainData>(elements: &[T]) -> &[u8] {
+    // safety: plain data has no padding, so every byte is initialised
+    unsafe {
+        std::slice::from_raw_parts(
+            elements.as_ptr() as *const u8,
+            std::mem::size_of_val(elements),
+        )
+    }
+}
+
+impl<T: PlainData> MyVector<T> {
+    /// Writes the vector in the binary format described in [crate::my_vector::persistence]
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), PersistenceError> {
+        let header = Header {
+            width: T::WIDTH,
+            endianness: NATIVE_ENDIAN,
+            length: self.len() as u64,
+        };
+        writer.write_all(&header.encode())?;
+
+        let payload = as_bytes(self.as_slice());
+        let mut checksum = Checksum::new();
+        for chunk in payload.chunks(CHUNK_LEN) {
+            checksum.update(chunk);
+            writer.write_all(chunk)?;
+        }
+        writer.write_all(&checksum.0.to_le_bytes())?;
+
+        Ok(())
+    }
+
+    /// Reads a vector written by [MyVector::write_to], converting the endianness if needed
+    pub fn read_from(mut reader: impl Read) -> Result<MyVector<T>, PersistenceError> {
+        let mut header = [0; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        let header = Header::decode::<T>(&header)?;
+        let mut remaining = header.payload_len()?;
+
+        let width = T::WIDTH as usize;
+        let mut v = MyVector::new();
+        let mut checksum = Checksum::new();
+        let mut buffer = vec![0_u8; CHUNK_LEN - CHUNK_LEN % width];
+        while remaining > 0 {
+            let chunk = &mut buffer[..std::cmp::min(remaining, CHUNK_LEN - CHUNK_LEN % width)];
+            reader.read_exact(chunk)?;
+            checksum.update(chunk);
+
+            // reserving per chunk avoids trusting a corrupt length with a huge allocation
+            v.reserve(chunk.len() / width);
+            for bytes in chunk.chunks_exact(width) {
+                let element = unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) };
+                if header.endianness == NATIVE_ENDIAN {
+                    v.add(element);
+                } else {
+                    v.add(element.swap_bytes());
+                }
+            }
+
+            remaining -= chunk.len();
+        }
+
+        let mut expected = [0; CHECKSUM_LEN];
+        reader.read_exact(&mut expected)?;
+        let expected = u64::from_le_bytes(expected);
+        if expected != checksum.0 {
+            return Err(PersistenceError::ChecksumMismatch {
+                expected,
+                found: checksum.0,
+            });
+        }
+
+        Ok(v)
+    }
+}
+
+/// Reinterprets bytes written by [MyVector::write_to] as a slice, without copying.
+///
+/// This is meant for buffers that are already in memory, e.g. a memory-mapped file.
+/// The payload has to be in the native endianness and aligned for `T`; since the
+/// header is 16 bytes long, a buffer aligned to 16 bytes always satisfies the latter.
+pub fn view<T: PlainData>(bytes: &[u8]) -> Result<&[T], PersistenceError> {
+    if bytes.len() < HEADER_LEN {
+        return Err(PersistenceError::Truncated {
+            expected: HEADER_LEN,
+            found: bytes.len(),
+        });
+    }
+    let header = Header::decode::<T>(bytes[..HEADER_LEN].try_into().unwrap())?;
+    if header.endianness != NATIVE_ENDIAN {
+        return Err(PersistenceError::ForeignEndianness);
+    }
+
+    let payload_len = header.payload_len()?;
+    let expected = payload_len.checked_add(HEADER_LEN + CHECKSUM_LEN).ok_or(
+        PersistenceError::LengthOverflow {
+            length: header.length,
+        },
+    )?;
+    if bytes.len() < expected {
+        return Err(PersistenceError::Truncated {
+            expected,
+            found: bytes.len(),
+        });
+    }
+
+    let payload = &bytes[HEADER_LEN..HEADER_LEN + payload_len];
+    let alignment = std::mem::align_of::<T>();
+    if !(payload.as_ptr() as usize).is_multiple_of(alignment) {
+        return Err(PersistenceError::Misaligned { alignment });
+    }
+
+    let mut checksum = Checksum::new();
+    checksum.update(payload);
+    let stored = u64::from_le_bytes(
+        bytes[HEADER_LEN + payload_len..expected]
+            .try_into()
+            .unwrap(),
+    );
+    if stored != checksum.0 {
+        return Err(PersistenceError::ChecksumMismatch {
+            expected: stored,
+            found: checksum.0,
+        });
+    }
+
+    // safety: the payload is aligned, in bounds, and every bit pattern is a valid T
+    Ok(unsafe { std::slice::from_raw_parts(payload.as_ptr() as *const T, header.length as usize) })
+}
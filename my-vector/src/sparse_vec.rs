@@ -0,0 +1,219 @@
+use crate::my_vector::MyVector;
+use std::ops::{Add, Mul};
+
+/// SparseVec
+///
+/// A fixed length vector that only stores the entries differing from `T::default()`.
+///
+/// The stored entries are kept as two `MyVector`s sorted by index, so looking up an
+/// entry is a binary search in O(log n). Overwriting a stored entry is O(log n) as well,
+/// while storing a new entry or resetting one to the default also shifts the entries after it.
+pub struct SparseVec<T> {
+    len: usize,
+    indices: MyVector<usize>,
+    values: MyVector<T>,
+    default: T,
+}
+
+impl<T: Default + PartialEq> SparseVec<T> {
+    pub fn new(len: usize) -> Self {
+        SparseVec {
+            len,
+            indices: MyVector::new(),
+            values: MyVector::new(),
+            default: T::default(),
+        }
+    }
+
+    /// The length of the vector, including the entries that are not stored
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of entries that differ from the default
+    pub fn stored(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Finds the position of an index in the stored entries, or the position where it would go
+    fn search(&self, index: usize) -> Result<usize, usize> {
+        self.indices.as_slice().binary_search(&index)
+    }
+
+    pub fn get(&self, index: usize) -> &T {
+        assert!(
+            index < self.len,
+            "Index {} is out of bounds for length {}",
+            index,
+            self.len
+        );
+
+        match self.search(index) {
+            Ok(position) => self.values.get(position),
+            Err(_) => &self.default,
+        }
+    }
+
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(
+            index < self.len,
+            "Index {} is out of bounds for length {}",
+            index,
+            self.len
+        );
+
+        match (self.search(index), value == self.default) {
+            (Ok(position), true) => {
+                // the entry goes back to the default, so it no longer needs to be stored
+                self.indices.drop(position);
+                self.values.drop(position);
+            }
+            (Ok(position), false) => {
+                *self.values.get_mut(position) = value;
+            }
+            (Err(_), true) => {
+                // nothing stored and nothing to store
+            }
+            (Err(position), false) => {
+                self.indices.insert(position, index);
+                self.values.insert(position, value);
+            }
+        }
+    }
+
+    pub fn from_dense(dense: &MyVector<T>) -> Self
+    where
+        T: Clone,
+    {
+        let mut sparse = SparseVec::new(dense.len());
+        for (index, value) in dense.into_iter().enumerate() {
+            if *value != sparse.default {
+                // indices are visited in order, so appending keeps them sorted
+                sparse.indices.add(index);
+                sparse.values.add(value.clone());
+            }
+        }
+
+        sparse
+    }
+
+    pub fn to_dense(&self) -> MyVector<T>
+    where
+        T: Clone,
+    {
+        let mut dense = MyVector::with_capacity(self.len);
+        let mut entries = self.iter().peekable();
+        for index in 0..self.len {
+            match entries.next_if(|(i, _)| *i == index) {
+                Some((_, value)) => dense.add(value.clone()),
+                None => dense.add(T::default()),
+            }
+        }
+
+        dense
+    }
+
+    /// Iterates over the stored entries as `(index, value)` pairs, in order of index
+    pub fn iter(&self) -> SparseVecIterator<'_, T> {
+        SparseVecIterator {
+            sparse: self,
+            current: 0,
+        }
+    }
+
+    /// Computes the dot product by only visiting indices stored in both vectors
+    pub fn dot(&self, other: &SparseVec<T>) -> T
+    where
+        T: Clone + Add<Output = T> + Mul<Output = T>,
+    {
+        assert_eq!(
+            self.len, other.len,
+            "Cannot compute the dot product of vectors with different lengths"
+        );
+
+        let mut result = T::default();
+        let (mut a, mut b) = (0, 0);
+        while a < self.stored() && b < other.stored() {
+            let (index_a, index_b) = (*self.indices.get(a), *other.indices.get(b));
+            if index_a < index_b {
+                a += 1;
+            } else if index_a > index_b {
+                b += 1;
+            } else {
+                result = result + self.values.get(a).clone() * other.values.get(b).clone();
+                a += 1;
+                b += 1;
+            }
+        }
+
+        result
+    }
+}
+
+impl<'a, T> Add for &'a SparseVec<T>
+where
+    T: Default + PartialEq + Clone + Add<Output = T>,
+{
+    type Output = SparseVec<T>;
+
+    /// Adds element-wise by merging the stored entries of both vectors
+    fn add(self, other: &'a SparseVec<T>) -> SparseVec<T> {
+        assert_eq!(
+            self.len, other.len,
+            "Cannot add vectors with different lengths"
+        );
+
+        let mut sum = SparseVec::new(self.len);
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        loop {
+            let (index, value) = match (a.peek(), b.peek()) {
+                (None, None) => break,
+                (Some(_), None) => a.next().map(|(i, x)| (i, x.clone())).unwrap(),
+                (None, Some(_)) => b.next().map(|(i, x)| (i, x.clone())).unwrap(),
+                (Some((index_a, _)), Some((index_b, _))) => {
+                    if index_a < index_b {
+                        a.next().map(|(i, x)| (i, x.clone())).unwrap()
+                    } else if index_a > index_b {
+                        b.next().map(|(i, x)| (i, x.clone())).unwrap()
+                    } else {
+                        let (index, x) = a.next().unwrap();
+                        let (_, y) = b.next().unwrap();
+                        (index, x.clone() + y.clone())
+                    }
+                }
+            };
+
+            // entries can cancel each other out
+            if value != sum.default {
+                sum.indices.add(index);
+                sum.values.add(value);
+            }
+        }
+
+        sum
+    }
+}
+
+pub struct SparseVecIterator<'a, T> {
+    sparse: &'a SparseVec<T>,
+    current: usize,
+}
+
+impl<'a, T> Iterator for SparseVecIterator<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sparse.indices.has(self.current) {
+            let i = self.current;
+            self.current += 1;
+            Some((*self.sparse.indices.get(i), self.sparse.values.get(i)))
+        } else {
+            None
+        }
+    }
+}
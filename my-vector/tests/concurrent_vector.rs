@@ -0,0 +1,155 @@
+use my_vector::concurrent_vector::ConcurrentVector;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+
+#[cfg(not(miri))]
+const THREADS: usize = 8;
+#[cfg(miri)]
+const THREADS: usize = 3;
+
+#[cfg(not(miri))]
+const PUSHES_PER_THREAD: usize = 10_000;
+#[cfg(miri)]
+const PUSHES_PER_THREAD: usize = 20;
+
+#[test]
+fn a_new_concurrent_vector_starts_out_empty() {
+    let v: ConcurrentVector<usize> = ConcurrentVector::new();
+    assert_eq!(0, v.len());
+    assert!(v.is_empty());
+    assert_eq!(None, v.get(0));
+}
+
+#[test]
+fn pushing_returns_consecutive_indexes() {
+    let v = ConcurrentVector::new();
+    assert_eq!(0, v.push(10));
+    assert_eq!(1, v.push(20));
+    assert_eq!(2, v.push(30));
+
+    assert_eq!(3, v.len());
+    assert_eq!(Some(&10), v.get(0));
+    assert_eq!(Some(&20), v.get(1));
+    assert_eq!(Some(&30), v.get(2));
+    assert_eq!(None, v.get(3));
+}
+
+#[test]
+fn getting_the_largest_index_returns_none() {
+    let v = ConcurrentVector::new();
+    v.push(1_usize);
+
+    assert_eq!(None, v.get(usize::MAX));
+    assert_eq!(None, v.get(usize::MAX - 1));
+}
+
+#[test]
+fn elements_do_not_move_when_the_vector_grows() {
+    let v = ConcurrentVector::new();
+    v.push(1_usize);
+    let first = v.get(0).unwrap() as *const usize;
+
+    for n in 0..100 {
+        v.push(n);
+    }
+
+    assert_eq!(first, v.get(0).unwrap() as *const usize);
+    assert_eq!(Some(&1), v.get(0));
+}
+
+#[test]
+fn pushed_values_are_dropped_with_the_vector() {
+    let counter = Arc::new(());
+    {
+        let v = ConcurrentVector::new();
+        for _ in 0..10 {
+            v.push(Arc::clone(&counter));
+        }
+        assert_eq!(11, Arc::strong_count(&counter));
+    }
+    assert_eq!(1, Arc::strong_count(&counter));
+}
+
+#[test]
+fn heap_owning_values_can_be_pushed_and_read() {
+    let v = ConcurrentVector::new();
+    v.push(String::from("left"));
+    v.push(String::from("right"));
+
+    assert_eq!(Some(&String::from("left")), v.get(0));
+    assert_eq!(Some(&String::from("right")), v.get(1));
+}
+
+#[test]
+fn concurrent_pushes_are_all_stored_exactly_once() {
+    let v = Arc::new(ConcurrentVector::new());
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let v = Arc::clone(&v);
+            thread::spawn(move || {
+                let mut indexes = Vec::with_capacity(PUSHES_PER_THREAD);
+                for n in 0..PUSHES_PER_THREAD {
+                    let value = t * PUSHES_PER_THREAD + n;
+                    let index = v.push(value);
+                    assert_eq!(Some(&value), v.get(index));
+                    indexes.push(index);
+                }
+                indexes
+            })
+        })
+        .collect();
+
+    let mut indexes = HashSet::new();
+    for handle in handles {
+        for index in handle.join().unwrap() {
+            assert!(
+                indexes.insert(index),
+                "index {} was handed out twice",
+                index
+            );
+        }
+    }
+
+    let total = THREADS * PUSHES_PER_THREAD;
+    assert_eq!(total, v.len());
+
+    let values: HashSet<usize> = (0..total).map(|i| *v.get(i).unwrap()).collect();
+    assert_eq!(total, values.len());
+    assert!(values.iter().all(|x| *x < total));
+}
+
+#[test]
+fn concurrent_readers_see_either_nothing_or_the_pushed_value() {
+    let v = Arc::new(ConcurrentVector::new());
+
+    let writer = {
+        let v = Arc::clone(&v);
+        thread::spawn(move || {
+            for n in 0..PUSHES_PER_THREAD {
+                v.push(n * 2);
+            }
+        })
+    };
+
+    let readers: Vec<_> = (0..THREADS - 1)
+        .map(|_| {
+            let v = Arc::clone(&v);
+            thread::spawn(move || {
+                for index in 0..PUSHES_PER_THREAD {
+                    if let Some(value) = v.get(index) {
+                        assert_eq!(index * 2, *value);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    writer.join().unwrap();
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    assert_eq!(PUSHES_PER_THREAD, v.len());
+}
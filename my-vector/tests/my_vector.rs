@@ -3,7 +3,7 @@ use my_vector::myvec;
 
 #[test]
 fn an_new_vector_starts_out_empty() {
-    let v = MyVector::new();
+    let v: MyVector = MyVector::new();
     assert_eq!(0, v.len());
 }
 
@@ -79,4 +79,60 @@ fn elements_can_be_added_with_macro() {
     let v: MyVector = myvec! [1, 2, 3, 4, 5];
     assert_eq!(&5, v.get(4));
     assert_eq!(5, v.len());
-}
\ No newline at end of file
+}
+
+#[test]
+fn vectors_can_hold_other_element_types() {
+    let mut v: MyVector<String> = MyVector::new();
+    v.add(String::from("a"));
+    v.add(String::from("b"));
+    v.add(String::from("c"));
+    v.drop(1);
+
+    assert_eq!(2, v.len());
+    assert_eq!("a", v.get(0));
+    assert_eq!("c", v.get(1));
+}
+
+#[test]
+fn reserving_grows_the_capacity_once() {
+    let mut v: MyVector<u8> = MyVector::with_capacity(10);
+    assert_eq!(10, v.capacity());
+
+    for n in 0..10 {
+        v.add(n);
+    }
+    assert_eq!(10, v.capacity());
+    assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], v.as_slice());
+}
+
+#[test]
+fn reserving_repeatedly_grows_the_capacity_geometrically() {
+    let mut v: MyVector<u8> = MyVector::with_capacity(4);
+    for n in 0..4 {
+        v.add(n);
+    }
+
+    v.reserve(1);
+    assert_eq!(8, v.capacity());
+    v.reserve(20);
+    assert_eq!(24, v.capacity());
+}
+
+#[test]
+fn elements_can_be_inserted() {
+    let mut v: MyVector = myvec![1, 3];
+    v.insert(1, 2);
+    v.insert(0, 0);
+    v.insert(4, 4);
+
+    assert_eq!(&[0, 1, 2, 3, 4], v.as_slice());
+}
+
+#[test]
+fn elements_can_be_changed_in_place() {
+    let mut v: MyVector = myvec![1, 2];
+    *v.get_mut(1) = 5;
+
+    assert_eq!(&5, v.get(1));
+}
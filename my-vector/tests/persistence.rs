@@ -0,0 +1,217 @@
+use my_vector::my_vector::persistence::{view, PersistenceError, HEADER_LEN};
+use my_vector::my_vector::MyVector;
+use my_vector::myvec;
+
+fn written<T: my_vector::my_vector::persistence::PlainData>(v: &MyVector<T>) -> Vec<u8> {
+    let mut bytes = vec![];
+    v.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+/// Copies bytes into a buffer whose start is aligned to 16 bytes
+fn aligned(bytes: &[u8]) -> Vec<u128> {
+    let mut buffer = vec![0_u128; bytes.len().div_ceil(16)];
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr() as *mut u8, bytes.len());
+    }
+    buffer
+}
+
+fn as_bytes(buffer: &[u128], len: usize) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(buffer.as_ptr() as *const u8, len) }
+}
+
+#[test]
+fn an_empty_vector_round_trips() {
+    let v: MyVector<usize> = MyVector::new();
+    let bytes = written(&v);
+
+    let read = MyVector::<usize>::read_from(bytes.as_slice()).unwrap();
+    assert_eq!(0, read.len());
+}
+
+#[test]
+fn a_usize_vector_round_trips() {
+    let v: MyVector = myvec![1, 2, 3, usize::MAX];
+    let bytes = written(&v);
+
+    let read = MyVector::<usize>::read_from(bytes.as_slice()).unwrap();
+    assert_eq!(v.as_slice(), read.as_slice());
+}
+
+#[test]
+fn signed_vectors_round_trip() {
+    let mut v: MyVector<i16> = MyVector::new();
+    v.add(-1);
+    v.add(i16::MIN);
+    v.add(i16::MAX);
+
+    let read = MyVector::<i16>::read_from(written(&v).as_slice()).unwrap();
+    assert_eq!(&[-1, i16::MIN, i16::MAX], read.as_slice());
+}
+
+#[test]
+fn a_vector_larger_than_a_single_chunk_round_trips() {
+    let mut v: MyVector<u32> = MyVector::new();
+    for n in 0..10_000 {
+        v.add(n * 7);
+    }
+
+    let read = MyVector::<u32>::read_from(written(&v).as_slice()).unwrap();
+    assert_eq!(v.as_slice(), read.as_slice());
+}
+
+#[test]
+fn the_header_describes_the_payload() {
+    let mut v: MyVector<u32> = MyVector::new();
+    v.add(1);
+    v.add(2);
+    let bytes = written(&v);
+
+    assert_eq!(b"MYVC", &bytes[0..4]);
+    assert_eq!(4, bytes[5]);
+    assert_eq!(2, u64::from_le_bytes(bytes[8..16].try_into().unwrap()));
+    assert_eq!(HEADER_LEN + 2 * 4 + 8, bytes.len());
+}
+
+#[test]
+fn a_foreign_endianness_is_converted_while_reading() {
+    let mut v: MyVector<u32> = MyVector::new();
+    v.add(0x0102_0304);
+    let mut bytes = written(&v);
+
+    // rewrite the payload as if it came from a machine with the other endianness
+    bytes[6] = 1 - bytes[6];
+    bytes[HEADER_LEN..HEADER_LEN + 4].reverse();
+    let payload = bytes[HEADER_LEN..HEADER_LEN + 4].to_vec();
+    let mut checksum: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in payload {
+        checksum ^= byte as u64;
+        checksum = checksum.wrapping_mul(0x0100_0000_01b3);
+    }
+    bytes[HEADER_LEN + 4..].copy_from_slice(&checksum.to_le_bytes());
+
+    let read = MyVector::<u32>::read_from(bytes.as_slice()).unwrap();
+    assert_eq!(&[0x0102_0304], read.as_slice());
+    assert!(matches!(
+        view::<u32>(as_bytes(&aligned(&bytes), bytes.len())),
+        Err(PersistenceError::ForeignEndianness)
+    ));
+}
+
+#[test]
+fn invalid_magic_bytes_are_rejected() {
+    let v: MyVector = myvec![1];
+    let mut bytes = written(&v);
+    bytes[0] = b'X';
+
+    assert!(matches!(
+        MyVector::<usize>::read_from(bytes.as_slice()),
+        Err(PersistenceError::InvalidMagic { .. })
+    ));
+}
+
+#[test]
+fn unknown_versions_are_rejected() {
+    let v: MyVector = myvec![1];
+    let mut bytes = written(&v);
+    bytes[4] = 99;
+
+    assert!(matches!(
+        MyVector::<usize>::read_from(bytes.as_slice()),
+        Err(PersistenceError::UnsupportedVersion { found: 99 })
+    ));
+}
+
+#[test]
+fn a_non_zero_reserved_byte_is_rejected() {
+    let v: MyVector = myvec![1];
+    let mut bytes = written(&v);
+    bytes[7] = 1;
+
+    assert!(matches!(
+        MyVector::<usize>::read_from(bytes.as_slice()),
+        Err(PersistenceError::InvalidReservedByte { found: 1 })
+    ));
+}
+
+#[test]
+fn reading_with_another_element_width_is_rejected() {
+    let mut v: MyVector<u16> = MyVector::new();
+    v.add(1);
+
+    assert!(matches!(
+        MyVector::<u64>::read_from(written(&v).as_slice()),
+        Err(PersistenceError::ElementWidthMismatch {
+            expected: 8,
+            found: 2
+        })
+    ));
+}
+
+#[test]
+fn a_corrupted_payload_fails_the_checksum() {
+    let v: MyVector = myvec![1, 2, 3];
+    let mut bytes = written(&v);
+    bytes[HEADER_LEN] ^= 0xff;
+
+    assert!(matches!(
+        MyVector::<usize>::read_from(bytes.as_slice()),
+        Err(PersistenceError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn a_truncated_stream_is_an_io_error() {
+    let v: MyVector = myvec![1, 2, 3];
+    let bytes = written(&v);
+
+    assert!(matches!(
+        MyVector::<usize>::read_from(&bytes[..bytes.len() - 1]),
+        Err(PersistenceError::Io(_))
+    ));
+}
+
+#[test]
+fn an_aligned_buffer_can_be_viewed_without_copying() {
+    let mut v: MyVector<u64> = MyVector::new();
+    for n in 0..100 {
+        v.add(n * n);
+    }
+    let bytes = written(&v);
+    let buffer = aligned(&bytes);
+    let bytes = as_bytes(&buffer, bytes.len());
+
+    let viewed = view::<u64>(bytes).unwrap();
+    assert_eq!(v.as_slice(), viewed);
+    assert_eq!(bytes[HEADER_LEN..].as_ptr() as *const u64, viewed.as_ptr());
+}
+
+#[test]
+fn a_misaligned_buffer_cannot_be_viewed() {
+    let mut v: MyVector<u64> = MyVector::new();
+    v.add(1);
+    let bytes = written(&v);
+
+    let mut shifted = vec![0_u8];
+    shifted.extend_from_slice(&bytes);
+    let buffer = aligned(&shifted);
+    let bytes = &as_bytes(&buffer, shifted.len())[1..];
+
+    assert!(matches!(
+        view::<u64>(bytes),
+        Err(PersistenceError::Misaligned { alignment: 8 })
+    ));
+}
+
+#[test]
+fn a_truncated_buffer_cannot_be_viewed() {
+    let v: MyVector = myvec![1, 2];
+    let bytes = written(&v);
+    let buffer = aligned(&bytes);
+
+    assert!(matches!(
+        view::<usize>(as_bytes(&buffer, bytes.len() - 1)),
+        Err(PersistenceError::Truncated { .. })
+    ));
+}
@@ -0,0 +1,101 @@
+use my_vector::my_vector::MyVector;
+use my_vector::myvec;
+use my_vector::sparse_vec::SparseVec;
+
+#[test]
+fn a_new_sparse_vector_only_holds_defaults() {
+    let v: SparseVec<usize> = SparseVec::new(10);
+    assert_eq!(10, v.len());
+    assert_eq!(0, v.stored());
+    assert_eq!(&0, v.get(9));
+}
+
+#[test]
+fn set_entries_can_be_read() {
+    let mut v = SparseVec::new(100);
+    v.set(50, 5);
+    v.set(2, 1);
+    v.set(99, 9);
+
+    assert_eq!(3, v.stored());
+    assert_eq!(&5, v.get(50));
+    assert_eq!(&1, v.get(2));
+    assert_eq!(&9, v.get(99));
+    assert_eq!(&0, v.get(51));
+}
+
+#[test]
+fn stored_entries_can_be_overwritten() {
+    let mut v = SparseVec::new(10);
+    v.set(3, 1);
+    v.set(3, 2);
+
+    assert_eq!(1, v.stored());
+    assert_eq!(&2, v.get(3));
+}
+
+#[test]
+fn setting_the_default_removes_the_entry() {
+    let mut v = SparseVec::new(10);
+    v.set(3, 1);
+    v.set(3, 0);
+    v.set(4, 0);
+
+    assert_eq!(0, v.stored());
+    assert_eq!(&0, v.get(3));
+}
+
+#[test]
+#[should_panic]
+fn entries_beyond_the_length_cannot_be_set() {
+    let mut v = SparseVec::new(10);
+    v.set(10, 1);
+}
+
+#[test]
+fn iterating_visits_stored_entries_in_order() {
+    let mut v = SparseVec::new(10);
+    v.set(7, 70);
+    v.set(1, 10);
+    v.set(4, 40);
+
+    let entries: Vec<(usize, usize)> = v.iter().map(|(i, x)| (i, *x)).collect();
+    assert_eq!(vec![(1, 10), (4, 40), (7, 70)], entries);
+}
+
+#[test]
+fn dense_vectors_round_trip() {
+    let dense: MyVector = myvec![0, 0, 3, 0, 5, 0];
+    let sparse = SparseVec::from_dense(&dense);
+
+    assert_eq!(6, sparse.len());
+    assert_eq!(2, sparse.stored());
+    assert_eq!(dense.as_slice(), sparse.to_dense().as_slice());
+}
+
+#[test]
+fn the_dot_product_only_counts_shared_indices() {
+    let a = SparseVec::from_dense(&myvec![1, 0, 2, 0, 3]);
+    let b = SparseVec::from_dense(&myvec![0, 4, 5, 0, 6]);
+
+    assert_eq!(2 * 5 + 3 * 6, a.dot(&b));
+}
+
+#[test]
+fn sparse_vectors_can_be_added() {
+    let a: SparseVec<i64> = SparseVec::from_dense(&myvec![1, 0, 2, 0, -3]);
+    let b: SparseVec<i64> = SparseVec::from_dense(&myvec![0, 4, 5, 0, 3]);
+
+    let sum = &a + &b;
+    assert_eq!(&[1, 4, 7, 0, 0], sum.to_dense().as_slice());
+    // the entries at index 4 cancel each other out and are not stored
+    assert_eq!(3, sum.stored());
+}
+
+#[test]
+#[should_panic]
+fn vectors_of_different_lengths_cannot_be_added() {
+    let a: SparseVec<usize> = SparseVec::new(2);
+    let b: SparseVec<usize> = SparseVec::new(3);
+    let _ = &a + &b;
+}